@@ -1,10 +1,22 @@
+use std::{env, path::PathBuf};
+
 fn main() -> anyhow::Result<()> {
     println!("cargo:rerun-if-changed=../protobuf/proto");
 
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+
     tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("games_descriptor.bin"))
         .build_server(true)
         .build_client(true)
-        .compile(&["../protobuf/proto/games.proto"], &["../protobuf/proto"])?;
+        .compile(
+            &[
+                "../protobuf/proto/games.proto",
+                "../protobuf/proto/google/rpc/error_details.proto",
+                "../protobuf/proto/google/rpc/status.proto",
+            ],
+            &["../protobuf/proto"],
+        )?;
 
     Ok(())
 }
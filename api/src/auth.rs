@@ -0,0 +1,119 @@
+//! Bearer token authentication of the Games service.
+//!
+//! [`Tokens::authenticate`] runs as a tonic interceptor and attaches the [`Role`] of the
+//! caller to the request. Interceptors do not know which method is called, so handlers
+//! check the role they need with [`authorize`].
+
+use crate::shutdown::Shutdown;
+use dataspine::{secret_hash, Repo, Role};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tonic::{service::Interceptor, Request, Status};
+
+/// Revoked tokens keep working for at most this long.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Active tokens, kept in memory because interceptors can not wait for the database.
+#[derive(Clone)]
+pub struct Tokens {
+    anonymous_role: Option<Role>,
+    roles: Arc<RwLock<HashMap<String, Role>>>,
+}
+
+impl Tokens {
+    pub fn authenticate<T>(&self, mut request: Request<T>) -> Result<Request<T>, Status> {
+        let role = match request.metadata().get("authorization") {
+            None => self
+                .anonymous_role
+                .ok_or(Status::unauthenticated("Missing bearer token"))?,
+            Some(value) => {
+                let secret = value
+                    .to_str()
+                    .ok()
+                    .and_then(|value| value.strip_prefix("Bearer "))
+                    .ok_or(Status::unauthenticated("Malformed authorization header"))?;
+
+                self.roles
+                    .read()
+                    .unwrap()
+                    .get(&secret_hash(secret))
+                    .copied()
+                    .ok_or(Status::unauthenticated("Invalid bearer token"))?
+            }
+        };
+
+        request.extensions_mut().insert(role);
+
+        Ok(request)
+    }
+
+    /// Loads active tokens and reloads them periodically until shutdown.
+    /// Requests without a token get `anonymous_role`, or are rejected when it is `None`.
+    pub async fn load(
+        repo: Repo,
+        anonymous_role: Option<Role>,
+        shutdown: Shutdown,
+    ) -> Result<Self, playground::Error> {
+        let tokens = Self {
+            anonymous_role,
+            roles: Arc::default(),
+        };
+
+        tokens.refresh(&repo).await?;
+
+        let refreshed = tokens.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.requested() => break,
+                    _ = tokio::time::sleep(REFRESH_INTERVAL) => {},
+                };
+
+                if let Err(err) = refreshed.refresh(&repo).await {
+                    tracing::error!("API tokens refresh error: {err}");
+                }
+            }
+        });
+
+        Ok(tokens)
+    }
+
+    async fn refresh(&self, repo: &Repo) -> Result<(), playground::Error> {
+        let roles = repo
+            .list_api_tokens()
+            .await?
+            .into_iter()
+            .filter(|api_token| api_token.revoke_time.is_none())
+            .map(|api_token| (api_token.secret_hash, api_token.role))
+            .collect();
+
+        *self.roles.write().unwrap() = roles;
+
+        Ok(())
+    }
+}
+
+impl Interceptor for Tokens {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        self.authenticate(request)
+    }
+}
+
+pub fn authorize<T>(request: &Request<T>, required: Role) -> Result<(), Status> {
+    let role = request
+        .extensions()
+        .get::<Role>()
+        .ok_or(Status::unauthenticated("Request was not authenticated"))?;
+
+    if *role < required {
+        return Err(Status::permission_denied(format!(
+            "Requires {required} role, given {role}"
+        )));
+    }
+
+    Ok(())
+}
@@ -1,10 +1,17 @@
-use crate::playground::rpc;
+use crate::{
+    error_details::{self, Detail},
+    playground::rpc,
+};
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
-use playground::{Error, Game, PlayerScore, Round, State};
-use prost_types::Timestamp;
+use playground::{
+    coordinator::SubmitVisitsError, Error, Game, GameSummary, LateVisitPolicy,
+    NewShotClockParameters, Number, PlayerScore, Points, Reason, Round, ScoreCorrection, ShotClock,
+    State, TimelineStep,
+};
+use prost_types::{FieldMask, Timestamp};
 use std::{collections::HashMap, time::SystemTime};
-use tonic::Status;
+use tonic::{Code, Status};
 use uuid::Uuid;
 
 pub trait ToRpc<T> {
@@ -17,19 +24,10 @@ pub trait TryConvert<T> {
 
 impl ToRpc<rpc::Game> for Game {
     fn to_rpc(self) -> rpc::Game {
-        let state = self.state();
-
-        let (player, player_points_to_win) = match state {
-            State::NotStarted(state) => (
-                format!("Player{}", state.player_number()),
-                state.points_to_win().value().into(),
-            ),
-            State::InProgress(state) => (
-                format!("Player{}", state.player_number()),
-                state.points_to_win().value().into(),
-            ),
-            State::Finished(_) => ("".to_string(), 0),
-        };
+        let (player, player_points_to_win) = current_player(self.state());
+        let (abandon_reason, abandon_time) = abandonment(self.state());
+        let pace = self.pace();
+        let remaining_turn_time = self.remaining_turn_time(Utc::now());
 
         rpc::Game {
             id: self.id().unwrap().to_string(),
@@ -43,33 +41,198 @@ impl ToRpc<rpc::Game> for Game {
             player_details: player_details(&self),
             create_time: self.create_time().map(ToRpc::to_rpc),
             update_time: self.update_time().map(ToRpc::to_rpc),
+            state: self.state().to_rpc().into(),
+            abandon_reason,
+            abandon_time,
+            players_number: self.players_number().value() as i32,
+            points_limit: self.points_limit().value().into(),
+            average_visit_duration: pace.average_visit_duration().map(ToRpc::to_rpc),
+            duration: pace.duration().map(ToRpc::to_rpc),
+            shot_clock: self.shot_clock().map(ToRpc::to_rpc),
+            turn_start_time: self.turn_start_time().map(ToRpc::to_rpc),
+            remaining_turn_time: remaining_turn_time.map(ToRpc::to_rpc),
         }
     }
 }
 
-impl ToRpc<rpc::Point> for &PlayerScore {
-    fn to_rpc(self) -> rpc::Point {
+impl ToRpc<rpc::GameSummary> for GameSummary {
+    fn to_rpc(self) -> rpc::GameSummary {
+        let (player, player_points_to_win) = current_player(self.state());
+        let (abandon_reason, abandon_time) = abandonment(self.state());
+
+        rpc::GameSummary {
+            id: self.id().to_string(),
+            winner: self
+                .winner()
+                .map(|number| format!("Player{}", number))
+                .unwrap_or_default(),
+            player,
+            player_points_to_win,
+            create_time: Some(self.create_time().to_rpc()),
+            update_time: Some(self.update_time().to_rpc()),
+            start_time: self.start_time().map(ToRpc::to_rpc),
+            end_time: self.end_time().map(ToRpc::to_rpc),
+            players_number: self.players_number().value() as i32,
+            points_limit: self.points_limit().value().into(),
+            state: self.state().to_rpc().into(),
+            abandon_reason,
+            abandon_time,
+        }
+    }
+}
+
+impl ToRpc<rpc::GameState> for &State {
+    fn to_rpc(self) -> rpc::GameState {
         match self {
+            State::NotStarted(_) => rpc::GameState::NotStarted,
+            State::InProgress(_) => rpc::GameState::InProgress,
+            State::Finished(_) => rpc::GameState::Finished,
+            State::Abandoned(_) => rpc::GameState::Abandoned,
+        }
+    }
+}
+
+impl ToRpc<rpc::Point> for &Round {
+    fn to_rpc(self) -> rpc::Point {
+        let time = self.time().map(ToRpc::to_rpc);
+        let late = self.is_late();
+
+        match self.player_score() {
             PlayerScore::Regular(score) => rpc::Point {
                 kind: rpc::PointKind::Regular.into(),
                 value: score.points().value().into(),
+                time,
+                late,
             },
             PlayerScore::Overthrow(score) => rpc::Point {
                 kind: rpc::PointKind::Overthrow.into(),
                 value: score.points().value().into(),
+                time,
+                late,
             },
         }
     }
 }
 
+impl ToRpc<rpc::ShotClock> for &ShotClock {
+    fn to_rpc(self) -> rpc::ShotClock {
+        let late_visit_policy = match self.late_visit_policy() {
+            LateVisitPolicy::Flag => rpc::LateVisitPolicy::Flag,
+            LateVisitPolicy::ScoreZero => rpc::LateVisitPolicy::ScoreZero,
+        };
+
+        rpc::ShotClock {
+            turn_time_limit: Some(self.turn_time_limit().to_rpc()),
+            late_visit_policy: late_visit_policy.into(),
+        }
+    }
+}
+
+impl ToRpc<rpc::ScoreCorrection> for ScoreCorrection {
+    fn to_rpc(self) -> rpc::ScoreCorrection {
+        rpc::ScoreCorrection {
+            round_number: self.round_number().value() as i32,
+            player_number: self.player_number().value() as i32,
+            previous_points: self.previous_score().points().value().into(),
+            points: self.score().points().value().into(),
+            correct_time: Some(self.time().to_rpc()),
+        }
+    }
+}
+
+impl ToRpc<rpc::TimelineStep> for TimelineStep {
+    fn to_rpc(self) -> rpc::TimelineStep {
+        let (player, _) = current_player(self.state());
+        let winner = match self.state() {
+            State::Finished(state) => format!("Player{}", state.winner()),
+            _ => String::new(),
+        };
+
+        rpc::TimelineStep {
+            round_number: self.round().number().value() as i32,
+            player_number: self.round().player_number().value() as i32,
+            point: Some(self.round().to_rpc()),
+            state: self.state().to_rpc().into(),
+            player,
+            winner,
+            player_details: self
+                .points_to_win()
+                .iter()
+                .enumerate()
+                .map(|(index, points)| rpc::PlayerDetails {
+                    points_to_win: points.value().into(),
+                    name: format!("Player{}", index + 1),
+                })
+                .collect(),
+            time: self.time().map(ToRpc::to_rpc),
+        }
+    }
+}
+
 impl ToRpc<Status> for Error {
     fn to_rpc(self) -> Status {
         match self {
-            Error::AlreadyExists(description) => Status::already_exists(description),
-            Error::FailedPrecondition(description) => Status::failed_precondition(description),
-            Error::InvalidArgument(description) => Status::invalid_argument(description),
-            Error::NotFound(description) => Status::not_found(description),
-            Error::Unexpected(report) => Status::internal(report.to_string()),
+            Error::Aborted(reason, description) => {
+                error_details::status(Code::Aborted, description, reason.as_str(), None)
+            }
+            Error::AlreadyExists(reason, description) => {
+                error_details::status(Code::AlreadyExists, description, reason.as_str(), None)
+            }
+            Error::FailedPrecondition(reason, description) => error_details::status(
+                Code::FailedPrecondition,
+                description,
+                reason.as_str(),
+                Some(Detail::PreconditionFailure {
+                    subject: String::new(),
+                }),
+            ),
+            Error::InvalidArgument(reason, description) => error_details::status(
+                Code::InvalidArgument,
+                description,
+                reason.as_str(),
+                request_field(reason).map(|field| Detail::FieldViolation {
+                    field: field.to_string(),
+                }),
+            ),
+            Error::NotFound(reason, description) => {
+                error_details::status(Code::NotFound, description, reason.as_str(), None)
+            }
+            Error::Unexpected(report) => {
+                tracing::error!(error = ?report, "Unexpected error");
+                Status::internal(report.to_string())
+            }
+        }
+    }
+}
+
+impl ToRpc<Status> for SubmitVisitsError {
+    fn to_rpc(self) -> Status {
+        match self {
+            SubmitVisitsError::Visit {
+                error: Error::FailedPrecondition(reason, description),
+                index,
+            } => error_details::status(
+                Code::FailedPrecondition,
+                format!("Visit {index}: {description}"),
+                reason.as_str(),
+                Some(Detail::PreconditionFailure {
+                    subject: format!("visits[{index}]"),
+                }),
+            ),
+            SubmitVisitsError::Visit {
+                error: Error::InvalidArgument(reason, description),
+                index,
+            } => error_details::status(
+                Code::InvalidArgument,
+                format!("Visit {index}: {description}"),
+                reason.as_str(),
+                Some(Detail::FieldViolation {
+                    field: format!("visits[{index}].points"),
+                }),
+            ),
+            SubmitVisitsError::Visit { error, .. } | SubmitVisitsError::Other(error) => {
+                error.to_rpc()
+            }
         }
     }
 }
@@ -81,9 +244,191 @@ impl ToRpc<Timestamp> for DateTime<Utc> {
     }
 }
 
+impl ToRpc<prost_types::Duration> for chrono::Duration {
+    fn to_rpc(self) -> prost_types::Duration {
+        let seconds = self.num_seconds();
+        let nanos = (self - chrono::Duration::seconds(seconds))
+            .num_nanoseconds()
+            .unwrap_or_default();
+
+        prost_types::Duration {
+            seconds,
+            nanos: nanos as i32,
+        }
+    }
+}
+
 impl TryConvert<Uuid> for String {
     fn try_convert(self) -> Result<Uuid, Status> {
-        Uuid::parse_str(&self).map_err(|_err| Status::invalid_argument(format!("Uuid: {self}")))
+        Uuid::parse_str(&self).map_err(|_err| {
+            // Game ids are the only ids clients send.
+            error_details::status(
+                Code::InvalidArgument,
+                format!("Uuid: {self}"),
+                Reason::InvalidUuid.as_str(),
+                Some(Detail::FieldViolation {
+                    field: "game_id".to_string(),
+                }),
+            )
+        })
+    }
+}
+
+/// Number of a request field, `None` for 0 which proto3 sends when it is not set.
+pub fn optional_number(value: i32, field: &'static str) -> Result<Option<Number>, Status> {
+    if value == 0 {
+        return Ok(None);
+    }
+
+    usize::try_from(value)
+        .ok()
+        .and_then(|value| Number::new(value).ok())
+        .map(Some)
+        .ok_or_else(|| {
+            error_details::status(
+                Code::InvalidArgument,
+                format!("Number must be greater than 0. Given: {value}"),
+                Reason::NegativeNumber.as_str(),
+                Some(Detail::FieldViolation {
+                    field: field.to_string(),
+                }),
+            )
+        })
+}
+
+/// Number of a request field that must be set.
+pub fn number(value: i32, field: &'static str) -> Result<Number, Status> {
+    optional_number(value, field)?.ok_or_else(|| {
+        error_details::status(
+            Code::InvalidArgument,
+            "Number must be greater than 0. Given: 0".to_string(),
+            Reason::ZeroNumber.as_str(),
+            Some(Detail::FieldViolation {
+                field: field.to_string(),
+            }),
+        )
+    })
+}
+
+pub fn points(value: i32, field: &'static str) -> Result<Points, Status> {
+    u16::try_from(value).map(Points::new).map_err(|_err| {
+        error_details::status(
+            Code::InvalidArgument,
+            format!("Points out of range. Given: {value}"),
+            Reason::InvalidPoints.as_str(),
+            Some(Detail::FieldViolation {
+                field: field.to_string(),
+            }),
+        )
+    })
+}
+
+/// Shot clock of a request, `None` when unset. `field` names it in field violations.
+pub fn shot_clock(
+    value: Option<rpc::ShotClock>,
+    field: &'static str,
+) -> Result<Option<ShotClock>, Status> {
+    let Some(shot_clock) = value else {
+        return Ok(None);
+    };
+
+    let late_visit_policy = match shot_clock.late_visit_policy() {
+        rpc::LateVisitPolicy::Unset | rpc::LateVisitPolicy::Flag => LateVisitPolicy::Flag,
+        rpc::LateVisitPolicy::ScoreZero => LateVisitPolicy::ScoreZero,
+    };
+    let limit = shot_clock.turn_time_limit.unwrap_or_default();
+    let turn_time_limit = chrono::Duration::try_seconds(limit.seconds)
+        .and_then(|seconds| seconds.checked_add(&chrono::Duration::nanoseconds(limit.nanos.into())))
+        .ok_or_else(|| {
+            error_details::status(
+                Code::InvalidArgument,
+                format!("Turn time limit out of range. Given: {}s", limit.seconds),
+                Reason::InvalidTurnTimeLimit.as_str(),
+                Some(Detail::FieldViolation {
+                    field: format!("{field}.turn_time_limit"),
+                }),
+            )
+        })?;
+
+    ShotClock::new(NewShotClockParameters {
+        late_visit_policy,
+        turn_time_limit,
+    })
+    .map(Some)
+    .map_err(|err| match err {
+        Error::InvalidArgument(reason, description) => error_details::status(
+            Code::InvalidArgument,
+            description,
+            reason.as_str(),
+            Some(Detail::FieldViolation {
+                field: format!("{field}.turn_time_limit"),
+            }),
+        ),
+        err => err.to_rpc(),
+    })
+}
+
+/// Fields of `fields` named by the mask, all of them for an empty mask.
+pub fn update_mask(
+    mask: Option<FieldMask>,
+    fields: &[&'static str],
+) -> Result<Vec<&'static str>, Status> {
+    let paths = mask.map(|mask| mask.paths).unwrap_or_default();
+
+    if paths.is_empty() {
+        return Ok(fields.to_vec());
+    }
+
+    paths
+        .iter()
+        .map(|path| {
+            fields
+                .iter()
+                .find(|field| *field == path)
+                .copied()
+                .ok_or_else(|| {
+                    error_details::status(
+                        Code::InvalidArgument,
+                        format!("Unknown field: {path}"),
+                        Reason::UnknownField.as_str(),
+                        Some(Detail::FieldViolation {
+                            field: "update_mask".to_string(),
+                        }),
+                    )
+                })
+        })
+        .collect()
+}
+
+fn current_player(state: &State) -> (String, i32) {
+    match state {
+        State::NotStarted(state) => (
+            format!("Player{}", state.player_number()),
+            state.points_to_win().value().into(),
+        ),
+        State::InProgress(state) => (
+            format!("Player{}", state.player_number()),
+            state.points_to_win().value().into(),
+        ),
+        State::Abandoned(_) | State::Finished(_) => ("".to_string(), 0),
+    }
+}
+
+fn abandonment(state: &State) -> (String, Option<Timestamp>) {
+    match state {
+        State::Abandoned(state) => (state.reason().to_string(), Some(state.time().to_rpc())),
+        _ => (String::new(), None),
+    }
+}
+
+/// Request field a client can correct to avoid the error.
+fn request_field(reason: Reason) -> Option<&'static str> {
+    match reason {
+        Reason::PlayersNumberOverLimit => Some("settings.players_number"),
+        Reason::ScoreOverLimit => Some("points"),
+        Reason::InvalidTurnTimeLimit => Some("settings.shot_clock.turn_time_limit"),
+        Reason::ZeroPointsLimit => Some("settings.points_limit"),
+        _ => None,
     }
 }
 
@@ -105,10 +450,7 @@ fn rounds(game: &Game) -> Vec<rpc::Round> {
         .rev()
         .map(|(number, round)| rpc::Round {
             number: number.try_into().unwrap(),
-            points: round
-                .iter()
-                .map(|data| data.player_score().to_rpc())
-                .collect(),
+            points: round.iter().map(|data| data.to_rpc()).collect(),
         })
         .collect();
 
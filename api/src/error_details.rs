@@ -0,0 +1,73 @@
+//! Standard `google.rpc` error details, sent in the `grpc-status-details-bin` trailer
+//! so that clients can show their own messages for each reason and request field.
+
+use prost::Message;
+use prost_types::Any;
+use tonic::{Code, Status};
+
+pub mod rpc {
+    tonic::include_proto!("google.rpc");
+}
+
+/// `ErrorInfo` domain of all reasons this service returns.
+const DOMAIN: &str = "darts";
+
+const TYPE_URL_PREFIX: &str = "type.googleapis.com/google.rpc";
+
+pub enum Detail {
+    /// Path of the request field, like `visits[2].points`.
+    FieldViolation { field: String },
+    /// Part of the request the precondition applies to, empty for the whole request.
+    PreconditionFailure { subject: String },
+}
+
+/// Status with an `ErrorInfo` carrying `reason`, and an optional detail
+/// describing the request field or the precondition at fault.
+pub fn status(code: Code, description: String, reason: &str, detail: Option<Detail>) -> Status {
+    let mut details = vec![pack(
+        "ErrorInfo",
+        rpc::ErrorInfo {
+            reason: reason.to_string(),
+            domain: DOMAIN.to_string(),
+            metadata: Default::default(),
+        },
+    )];
+
+    match detail {
+        Some(Detail::FieldViolation { field }) => details.push(pack(
+            "BadRequest",
+            rpc::BadRequest {
+                field_violations: vec![rpc::bad_request::FieldViolation {
+                    field,
+                    description: description.clone(),
+                }],
+            },
+        )),
+        Some(Detail::PreconditionFailure { subject }) => details.push(pack(
+            "PreconditionFailure",
+            rpc::PreconditionFailure {
+                violations: vec![rpc::precondition_failure::Violation {
+                    r#type: reason.to_string(),
+                    subject,
+                    description: description.clone(),
+                }],
+            },
+        )),
+        None => {}
+    }
+
+    let status = rpc::Status {
+        code: code as i32,
+        message: description.clone(),
+        details,
+    };
+
+    Status::with_details(code, description, status.encode_to_vec().into())
+}
+
+fn pack(name: &str, message: impl Message) -> Any {
+    Any {
+        type_url: format!("{TYPE_URL_PREFIX}.{name}"),
+        value: message.encode_to_vec(),
+    }
+}
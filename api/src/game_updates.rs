@@ -0,0 +1,63 @@
+use crate::shutdown::Shutdown;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Number of updates a slow watcher may fall behind before it skips to the latest game state.
+const CAPACITY: usize = 64;
+
+/// Delay before listening again after the database listener failed.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Fans out game changes, made by any api instance, to watchers connected to this one.
+#[derive(Clone)]
+pub struct GameUpdates {
+    sender: broadcast::Sender<Uuid>,
+}
+
+impl Default for GameUpdates {
+    /// Updates published only within this process.
+    fn default() -> Self {
+        let (sender, _receiver) = broadcast::channel(CAPACITY);
+
+        Self { sender }
+    }
+}
+
+impl GameUpdates {
+    /// Forwards database notifications until shutdown, then releases the listener connection.
+    pub fn spawn(mut listener: dataspine::GameUpdates, shutdown: Shutdown) -> Self {
+        let updates = Self::default();
+        let publisher = updates.clone();
+
+        tokio::spawn(async move {
+            loop {
+                // Closing the pool on shutdown fails the listener, which is not worth reporting.
+                let update = tokio::select! {
+                    biased;
+                    _ = shutdown.requested() => break,
+                    update = listener.recv() => update,
+                };
+
+                match update {
+                    Ok(game_id) => publisher.publish(game_id),
+                    Err(err) => {
+                        tracing::error!("Game updates listener error: {err}");
+                        tokio::time::sleep(RETRY_DELAY).await;
+                    }
+                }
+            }
+        });
+
+        updates
+    }
+
+    pub fn publish(&self, game_id: Uuid) {
+        // No receivers means nobody is watching right now.
+        let _ = self.sender.send(game_id);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Uuid> {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,39 @@
+use axum::http::{header::InvalidHeaderValue, HeaderName, HeaderValue};
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+/// Same preflight cache duration as `tonic_web::enable` uses.
+const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+const ALLOW_HEADERS: [&str; 5] = [
+    "x-grpc-web",
+    "content-type",
+    "x-user-agent",
+    "grpc-timeout",
+    "authorization",
+];
+
+const EXPOSE_HEADERS: [&str; 3] = ["grpc-status", "grpc-message", "grpc-status-details-bin"];
+
+/// CORS for gRPC-Web browser clients.
+/// Any origin is allowed when `allowed_origins` is empty. Clients authenticate with the
+/// `authorization` header rather than cookies, so credentials are never allowed.
+pub fn cors_layer(allowed_origins: &[String]) -> Result<CorsLayer, InvalidHeaderValue> {
+    let allowed_origins = allowed_origins
+        .iter()
+        .map(|origin| HeaderValue::from_str(origin))
+        .collect::<Result<Vec<HeaderValue>, _>>()?;
+    let allow_origin = if allowed_origins.is_empty() {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(allowed_origins)
+    };
+
+    let layer = CorsLayer::new()
+        .allow_origin(allow_origin)
+        .max_age(MAX_AGE)
+        .allow_headers(ALLOW_HEADERS.map(HeaderName::from_static))
+        .expose_headers(EXPOSE_HEADERS.map(HeaderName::from_static));
+
+    Ok(layer)
+}
@@ -0,0 +1,35 @@
+use crate::playground::{rpc::games_server::GamesServer, Server};
+use dataspine::Repo;
+use std::time::Duration;
+use tonic::server::NamedService;
+use tonic_health::{server::HealthReporter, ServingStatus};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Database check taking longer than this counts as failed.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Reports the Games service, and the server as a whole, as NOT_SERVING
+/// while the database is unreachable.
+pub async fn report_database_health(repo: Repo, mut reporter: HealthReporter) {
+    loop {
+        let status = match tokio::time::timeout(CHECK_TIMEOUT, repo.ping()).await {
+            Ok(Ok(())) => ServingStatus::Serving,
+            Ok(Err(err)) => {
+                tracing::warn!("Database health check failed: {err}");
+                ServingStatus::NotServing
+            }
+            Err(_elapsed) => {
+                tracing::warn!("Database health check timed out");
+                ServingStatus::NotServing
+            }
+        };
+
+        reporter.set_service_status("", status).await;
+        reporter
+            .set_service_status(GamesServer::<Server>::NAME, status)
+            .await;
+
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
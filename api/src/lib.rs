@@ -0,0 +1,16 @@
+//! Games service shared by the api and the api mock, with the HTTP gateway and
+//! middleware of the api.
+
+mod convert;
+mod error_details;
+
+pub mod auth;
+pub mod game_updates;
+pub mod grpc_web;
+pub mod health;
+pub mod metrics;
+pub mod playground;
+pub mod rest;
+pub mod shutdown;
+pub mod store;
+pub mod telemetry;
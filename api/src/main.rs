@@ -1,29 +1,135 @@
-mod convert;
-mod playground;
-
+use api::{
+    auth::Tokens, game_updates::GameUpdates, grpc_web, health, metrics::Metrics, playground, rest,
+    shutdown::Shutdown, store::Store, telemetry,
+};
 use clap::Parser;
-use dataspine::Repo;
+use dataspine::{ConnectParameters, Repo, Role};
+use server_config::{ServerArgs, ServerConfig};
+use std::{sync::Arc, time::Duration};
+use tonic::service::interceptor::InterceptedService;
+use tonic_web::GrpcWebLayer;
+use tower::ServiceBuilder;
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::{DefaultOnResponse, TraceLayer},
+};
+use tracing::Level;
+
+const POOL_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Parser)]
 struct Args {
-    #[arg(long)]
-    database_url: String,
+    #[command(flatten)]
+    server: ServerArgs,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let Args { database_url } = Args::parse();
+    let config = ServerConfig::load(Args::parse().server)?;
+    config.init_logging();
+
+    let database_url = config
+        .database_url
+        .as_deref()
+        .ok_or(anyhow::anyhow!("Missing database URL"))?;
+    let anonymous_role = config
+        .anonymous_role
+        .as_deref()
+        .map(str::parse::<Role>)
+        .transpose()?;
+    let cors_layer = grpc_web::cors_layer(&config.cors_allowed_origins)?;
 
-    let repo = Repo::from_database_url(&database_url).await?;
-    let addr = "[::]:50051".parse()?;
-    println!("Running playground using http://{addr}");
+    let repo = Repo::connect(ConnectParameters {
+        database_url,
+        pool_size: config.database_pool_size,
+    })
+    .await?;
+    let shutdown = Shutdown::listen();
+    let tokens = Tokens::load(repo.clone(), anonymous_role, shutdown.clone()).await?;
+    let game_updates = GameUpdates::spawn(repo.listen_game_updates().await?, shutdown.clone());
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    tokio::spawn(health::report_database_health(
+        repo.clone(),
+        health_reporter,
+    ));
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(playground::rpc::FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build()?;
+    let store = Store::Postgres(repo.clone());
+    let metrics = Arc::new(Metrics::new(store.clone())?);
+    let server = Arc::new(playground::Server::new(
+        store,
+        game_updates,
+        metrics.clone(),
+        shutdown.clone(),
+    ));
+    tracing::info!("Running playground using {}", config.grpc_url());
+    tracing::info!("Running HTTP gateway using http://{}", config.http_address);
 
-    tonic::transport::Server::builder()
-        .add_service(playground::rpc::games_server::GamesServer::new(
-            playground::Server::new(repo),
+    let grpc = config
+        .grpc_server()?
+        .accept_http1(true)
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                .layer(
+                    TraceLayer::new_for_grpc()
+                        .make_span_with(telemetry::make_span)
+                        .on_response(DefaultOnResponse::new().level(Level::INFO)),
+                )
+                .layer(PropagateRequestIdLayer::x_request_id()),
+        )
+        .layer(cors_layer)
+        .layer(GrpcWebLayer::new())
+        .layer(metrics.layer())
+        .add_service(health_service)
+        .add_service(reflection_service)
+        .add_service(InterceptedService::new(
+            playground::rpc::games_server::GamesServer::from_arc(server.clone()),
+            tokens.clone(),
         ))
-        .serve(addr)
-        .await?;
+        .serve_with_shutdown(config.grpc_address, shutdown.requested());
+    let http = axum::Server::bind(&config.http_address)
+        .serve(
+            rest::router(server, tokens)
+                .layer(
+                    ServiceBuilder::new()
+                        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                        .layer(
+                            TraceLayer::new_for_http()
+                                .make_span_with(telemetry::make_span)
+                                .on_response(DefaultOnResponse::new().level(Level::INFO)),
+                        )
+                        .layer(PropagateRequestIdLayer::x_request_id()),
+                )
+                .merge(metrics.router())
+                .into_make_service(),
+        )
+        .with_graceful_shutdown(shutdown.requested());
+
+    let servers = async {
+        tokio::try_join!(async { grpc.await.map_err(anyhow::Error::from) }, async {
+            http.await.map_err(anyhow::Error::from)
+        },)
+    };
+
+    let result = tokio::select! {
+        result = servers => result.map(|_| ()),
+        _ = shutdown.drain_elapsed(config.drain_timeout) => {
+            tracing::warn!("Drain timeout elapsed, exiting with requests in flight");
+            Ok(())
+        }
+    };
+
+    // Requests cut off by the drain timeout may still hold connections, closing waits for them.
+    if tokio::time::timeout(POOL_CLOSE_TIMEOUT, repo.close())
+        .await
+        .is_err()
+    {
+        tracing::warn!("Database connections still in use, exiting without closing them");
+    }
+    tracing::info!("Shut down");
 
-    Ok(())
+    result
 }
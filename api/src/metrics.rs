@@ -0,0 +1,221 @@
+//! Prometheus metrics served on `/metrics` of the HTTP gateway.
+
+use crate::store::Store;
+use axum::{
+    extract::State,
+    http::{header, Request, Response, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use prometheus::{
+    HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+use tonic::Code;
+use tower::{Layer, Service};
+
+pub struct Metrics {
+    database_connections: IntGauge,
+    database_idle_connections: IntGauge,
+    database_max_connections: IntGauge,
+    games_created: IntCounter,
+    games_finished: IntCounter,
+    registry: Registry,
+    rpc_duration: HistogramVec,
+    rpc_handled: IntCounterVec,
+    scores_counted: IntCounter,
+    store: Store,
+}
+
+/// Counts gRPC calls and measures their latency.
+#[derive(Clone)]
+pub struct RpcMetricsLayer {
+    metrics: Arc<Metrics>,
+}
+
+#[derive(Clone)]
+pub struct RpcMetricsService<S> {
+    inner: S,
+    metrics: Arc<Metrics>,
+}
+
+impl Metrics {
+    pub fn new(store: Store) -> Result<Self, prometheus::Error> {
+        let registry = Registry::new();
+
+        let metrics = Self {
+            database_connections: IntGauge::new(
+                "database_connections",
+                "Open database connections, idle or in use",
+            )?,
+            database_idle_connections: IntGauge::new(
+                "database_idle_connections",
+                "Database connections waiting to be used",
+            )?,
+            database_max_connections: IntGauge::new(
+                "database_max_connections",
+                "Size limit of the database pool",
+            )?,
+            games_created: IntCounter::new("games_created_total", "Created games")?,
+            games_finished: IntCounter::new("games_finished_total", "Games won by a player")?,
+            registry,
+            rpc_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "grpc_server_handling_seconds",
+                    "Time until the response headers of a gRPC call are sent, HTTP gateway calls are not included",
+                ),
+                &["grpc_service", "grpc_method"],
+            )?,
+            rpc_handled: IntCounterVec::new(
+                Opts::new(
+                    "grpc_server_handled_total",
+                    "Completed gRPC calls, HTTP gateway calls are not included",
+                ),
+                &["grpc_service", "grpc_method", "grpc_code"],
+            )?,
+            scores_counted: IntCounter::new("scores_counted_total", "Counted player scores")?,
+            store,
+        };
+
+        metrics.register()?;
+
+        Ok(metrics)
+    }
+
+    pub fn game_created(&self) {
+        self.games_created.inc();
+    }
+
+    pub fn game_finished(&self) {
+        self.games_finished.inc();
+    }
+
+    pub fn layer(self: &Arc<Self>) -> RpcMetricsLayer {
+        RpcMetricsLayer {
+            metrics: self.clone(),
+        }
+    }
+
+    pub fn router(self: Arc<Self>) -> Router {
+        Router::new()
+            .route("/metrics", get(metrics))
+            .with_state(self)
+    }
+
+    pub fn scores_counted(&self, count: usize) {
+        self.scores_counted.inc_by(count as u64);
+    }
+
+    fn observe_rpc(&self, path: &str, code: Code, started: Instant) {
+        let (service, method) = path
+            .trim_start_matches('/')
+            .split_once('/')
+            .unwrap_or(("unknown", "unknown"));
+
+        self.rpc_handled
+            .with_label_values(&[service, method, &format!("{code:?}")])
+            .inc();
+        self.rpc_duration
+            .with_label_values(&[service, method])
+            .observe(started.elapsed().as_secs_f64());
+    }
+
+    fn register(&self) -> Result<(), prometheus::Error> {
+        self.registry
+            .register(Box::new(self.database_connections.clone()))?;
+        self.registry
+            .register(Box::new(self.database_idle_connections.clone()))?;
+        self.registry
+            .register(Box::new(self.database_max_connections.clone()))?;
+        self.registry
+            .register(Box::new(self.games_created.clone()))?;
+        self.registry
+            .register(Box::new(self.games_finished.clone()))?;
+        self.registry
+            .register(Box::new(self.rpc_duration.clone()))?;
+        self.registry.register(Box::new(self.rpc_handled.clone()))?;
+        self.registry
+            .register(Box::new(self.scores_counted.clone()))?;
+
+        Ok(())
+    }
+
+    fn render(&self) -> Result<String, prometheus::Error> {
+        if let Some(pool) = self.store.pool_status() {
+            self.database_connections.set(pool.size.into());
+            self.database_idle_connections.set(pool.idle as i64);
+            self.database_max_connections.set(pool.max_size.into());
+        }
+
+        TextEncoder::new().encode_to_string(&self.registry.gather())
+    }
+}
+
+impl<S> Layer<S> for RpcMetricsLayer {
+    type Service = RpcMetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcMetricsService {
+            inner,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+impl<S, B, ResBody> Service<Request<B>> for RpcMetricsService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let metrics = self.metrics.clone();
+        let path = request.uri().path().to_string();
+        let started = Instant::now();
+        let response = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = response.await?;
+
+            // Failed calls are answered with the status in the headers, successful ones
+            // carry it in the trailers, which would require waiting for the whole body.
+            let code = response
+                .headers()
+                .get("grpc-status")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<i32>().ok())
+                .map(Code::from)
+                .unwrap_or(Code::Ok);
+
+            metrics.observe_rpc(&path, code, started);
+
+            Ok(response)
+        })
+    }
+}
+
+async fn metrics(State(metrics): State<Arc<Metrics>>) -> impl IntoResponse {
+    match metrics.render() {
+        Ok(body) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+            body,
+        )
+            .into_response(),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    }
+}
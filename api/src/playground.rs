@@ -1,70 +1,225 @@
-use crate::convert::{ToRpc, TryConvert};
-use dataspine::Repo;
-use playground::{self, coordinator, Number, Points, Score};
+use crate::{
+    auth,
+    convert::{self, optional_number, ToRpc, TryConvert},
+    game_updates::GameUpdates,
+    metrics::Metrics,
+    shutdown::Shutdown,
+    store::Store,
+};
+use dataspine::Role;
+use playground::{
+    self,
+    coordinator::{self, SubmitVisitsError},
+    ExpectedTurn, Number, Points, Score,
+};
+use std::sync::Arc;
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
+use tracing::Instrument;
 
 pub mod rpc {
     tonic::include_proto!("proto.playground"); // The string specified here must match the proto package name
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("games_descriptor");
 }
 
 pub struct Server {
-    repo: Repo,
+    game_updates: GameUpdates,
+    metrics: Arc<Metrics>,
+    shutdown: Shutdown,
+    store: Store,
 }
 
 #[tonic::async_trait]
 impl rpc::games_server::Games for Server {
+    type WatchGameStream = ReceiverStream<Result<rpc::Game, Status>>;
+
+    async fn abandon_game(
+        &self,
+        request: Request<rpc::AbandonGameRequest>,
+    ) -> Result<Response<rpc::Game>, Status> {
+        auth::authorize(&request, Role::Scorer)?;
+
+        let rpc::AbandonGameRequest {
+            game_id,
+            reason,
+            request_id,
+        } = request.into_inner();
+
+        let game = coordinator::abandon_game(coordinator::AbandonGameParameters {
+            game_id: game_id.try_convert()?,
+            games: &self.store,
+            reason,
+            request_id: optional(&request_id),
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
+
+        Ok(Response::new(game.to_rpc()))
+    }
+
     async fn cancel_last_score(
         &self,
-        _request: Request<rpc::CancelLastScoreRequest>,
+        request: Request<rpc::CancelLastScoreRequest>,
     ) -> Result<Response<rpc::CancelLastScoreResponse>, Status> {
-        // Ok(Response::new(rpc::CancelLastScoreResponse::default()))
-        Err(Status::unimplemented("Not yet implemented"))
+        auth::authorize(&request, Role::Scorer)?;
+
+        let rpc::CancelLastScoreRequest {
+            game_id,
+            request_id,
+        } = request.into_inner();
+
+        let game = coordinator::cancel_last_score(coordinator::CancelLastScoreParameters {
+            games: &self.store,
+            game_id: game_id.try_convert()?,
+            request_id: optional(&request_id),
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
+
+        Ok(Response::new(rpc::CancelLastScoreResponse {
+            game: Some(game.into_game().to_rpc()),
+        }))
+    }
+
+    async fn correct_score(
+        &self,
+        request: Request<rpc::CorrectScoreRequest>,
+    ) -> Result<Response<rpc::CorrectScoreResponse>, Status> {
+        auth::authorize(&request, Role::Scorer)?;
+
+        let rpc::CorrectScoreRequest {
+            game_id,
+            round_number,
+            player_number,
+            points,
+            request_id,
+        } = request.into_inner();
+
+        let score = Score::new(points as u16).map_err(ToRpc::to_rpc)?;
+
+        let game = coordinator::correct_score(coordinator::CorrectScoreParameters {
+            game_id: game_id.try_convert()?,
+            games: &self.store,
+            player_number: convert::number(player_number, "player_number")?,
+            request_id: optional(&request_id),
+            round_number: convert::number(round_number, "round_number")?,
+            score,
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
+
+        if game.is_finished() {
+            self.metrics.game_finished();
+        }
+
+        Ok(Response::new(rpc::CorrectScoreResponse {
+            game: Some(game.into_game().to_rpc()),
+        }))
     }
 
     async fn count_points(
         &self,
         request: Request<rpc::CountPointsRequest>,
     ) -> Result<Response<rpc::CountPointsResponse>, Status> {
-        let rpc::CountPointsRequest { game_id, points } = request.into_inner();
+        auth::authorize(&request, Role::Scorer)?;
+
+        let rpc::CountPointsRequest {
+            game_id,
+            points,
+            request_id,
+            expected_player_number,
+            expected_round_number,
+        } = request.into_inner();
 
         let score = Score::new(points as u16).map_err(ToRpc::to_rpc)?;
 
         let game = coordinator::count_score(coordinator::CountScoreParameters {
-            games: &self.repo,
+            expected_turn: ExpectedTurn {
+                player_number: optional_number(expected_player_number, "expected_player_number")?,
+                round_number: optional_number(expected_round_number, "expected_round_number")?,
+            },
+            games: &self.store,
             game_id: game_id.try_convert()?,
+            request_id: optional(&request_id),
             score,
         })
         .await
         .map_err(ToRpc::to_rpc)?;
 
+        if game.is_applied() {
+            self.metrics.scores_counted(1);
+        }
+        if game.is_finished() {
+            self.metrics.game_finished();
+        }
+
         Ok(Response::new(rpc::CountPointsResponse {
-            game: Some(game.to_rpc()),
+            game: Some(game.into_game().to_rpc()),
         }))
     }
 
     async fn create_game(
         &self,
-        _request: Request<rpc::CreateGameRequest>,
+        request: Request<rpc::CreateGameRequest>,
     ) -> Result<Response<rpc::Game>, Status> {
+        auth::authorize(&request, Role::Scorer)?;
+
+        let rpc::CreateGameRequest {
+            request_id,
+            shot_clock,
+        } = request.into_inner();
+
         let game = coordinator::initialize_game(coordinator::InitializeGameParameters {
             players_number: Number::new(2).unwrap(),
             points_limit: Points::new(301),
-            games: &self.repo,
+            games: &self.store,
+            request_id: optional(&request_id),
+            shot_clock: convert::shot_clock(shot_clock, "shot_clock")?,
         })
         .await
         .map_err(ToRpc::to_rpc)?;
 
-        Ok(Response::new(game.to_rpc()))
+        if game.is_applied() {
+            self.metrics.game_created();
+        }
+
+        Ok(Response::new(game.into_game().to_rpc()))
+    }
+
+    async fn delete_game(
+        &self,
+        request: Request<rpc::DeleteGameRequest>,
+    ) -> Result<Response<rpc::DeleteGameResponse>, Status> {
+        auth::authorize(&request, Role::Admin)?;
+
+        let rpc::DeleteGameRequest {
+            game_id,
+            request_id,
+        } = request.into_inner();
+
+        coordinator::delete_game(coordinator::DeleteGameParameters {
+            games: &self.store,
+            game_id: game_id.try_convert()?,
+            request_id: optional(&request_id),
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
+
+        Ok(Response::new(rpc::DeleteGameResponse {}))
     }
 
     async fn get_game(
         &self,
         request: Request<rpc::GetGameRequest>,
     ) -> Result<Response<rpc::Game>, Status> {
+        auth::authorize(&request, Role::Viewer)?;
+
         let rpc::GetGameRequest { game_id } = request.into_inner();
 
         let game = coordinator::get_game(coordinator::GetGameParameters {
-            games: &self.repo,
+            games: &self.store,
             game_id: game_id.try_convert()?,
         })
         .await
@@ -73,23 +228,273 @@ impl rpc::games_server::Games for Server {
         Ok(Response::new(game.to_rpc()))
     }
 
+    async fn get_game_timeline(
+        &self,
+        request: Request<rpc::GetGameTimelineRequest>,
+    ) -> Result<Response<rpc::GetGameTimelineResponse>, Status> {
+        auth::authorize(&request, Role::Viewer)?;
+
+        let rpc::GetGameTimelineRequest { game_id } = request.into_inner();
+
+        let steps = coordinator::get_game_timeline(coordinator::GetGameTimelineParameters {
+            games: &self.store,
+            game_id: game_id.try_convert()?,
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
+
+        Ok(Response::new(rpc::GetGameTimelineResponse {
+            steps: steps.into_iter().map(ToRpc::to_rpc).collect(),
+        }))
+    }
+
     async fn list_games(
         &self,
-        _request: Request<rpc::ListGamesRequest>,
+        request: Request<rpc::ListGamesRequest>,
     ) -> Result<Response<rpc::ListGamesResponse>, Status> {
-        let game_previews =
-            coordinator::list_games(coordinator::ListGamesParameters { games: &self.repo })
-                .await
-                .map_err(ToRpc::to_rpc)?;
+        let include_deleted = request.get_ref().include_deleted;
+        auth::authorize(&request, list_role(include_deleted))?;
+
+        let game_previews = coordinator::list_games(coordinator::ListGamesParameters {
+            games: &self.store,
+            include_deleted,
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
 
         let games = game_previews.into_iter().map(ToRpc::to_rpc).collect();
 
         Ok(Response::new(rpc::ListGamesResponse { games }))
     }
+
+    async fn list_game_summaries(
+        &self,
+        request: Request<rpc::ListGameSummariesRequest>,
+    ) -> Result<Response<rpc::ListGameSummariesResponse>, Status> {
+        let include_deleted = request.get_ref().include_deleted;
+        auth::authorize(&request, list_role(include_deleted))?;
+
+        let summaries =
+            coordinator::list_game_summaries(coordinator::ListGameSummariesParameters {
+                games: &self.store,
+                include_deleted,
+            })
+            .await
+            .map_err(ToRpc::to_rpc)?;
+
+        let game_summaries = summaries.into_iter().map(ToRpc::to_rpc).collect();
+
+        Ok(Response::new(rpc::ListGameSummariesResponse {
+            game_summaries,
+        }))
+    }
+
+    async fn list_score_corrections(
+        &self,
+        request: Request<rpc::ListScoreCorrectionsRequest>,
+    ) -> Result<Response<rpc::ListScoreCorrectionsResponse>, Status> {
+        auth::authorize(&request, Role::Viewer)?;
+
+        let rpc::ListScoreCorrectionsRequest { game_id } = request.into_inner();
+
+        let corrections =
+            coordinator::list_score_corrections(coordinator::ListScoreCorrectionsParameters {
+                game_id: game_id.try_convert()?,
+                games: &self.store,
+            })
+            .await
+            .map_err(ToRpc::to_rpc)?;
+
+        let score_corrections = corrections.into_iter().map(ToRpc::to_rpc).collect();
+
+        Ok(Response::new(rpc::ListScoreCorrectionsResponse {
+            score_corrections,
+        }))
+    }
+
+    async fn submit_visits(
+        &self,
+        request: Request<rpc::SubmitVisitsRequest>,
+    ) -> Result<Response<rpc::SubmitVisitsResponse>, Status> {
+        auth::authorize(&request, Role::Scorer)?;
+
+        let rpc::SubmitVisitsRequest {
+            game_id,
+            visits,
+            request_id,
+            expected_player_number,
+            expected_round_number,
+        } = request.into_inner();
+
+        let scores = visits
+            .iter()
+            .enumerate()
+            .map(|(index, visit)| {
+                Score::new(visit.points as u16)
+                    .map_err(|error| SubmitVisitsError::Visit { error, index }.to_rpc())
+            })
+            .collect::<Result<Vec<Score>, Status>>()?;
+
+        let game = coordinator::submit_visits(coordinator::SubmitVisitsParameters {
+            expected_turn: ExpectedTurn {
+                player_number: optional_number(expected_player_number, "expected_player_number")?,
+                round_number: optional_number(expected_round_number, "expected_round_number")?,
+            },
+            game_id: game_id.try_convert()?,
+            games: &self.store,
+            request_id: optional(&request_id),
+            scores,
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
+
+        if game.is_applied() {
+            self.metrics.scores_counted(visits.len());
+        }
+        if game.is_finished() {
+            self.metrics.game_finished();
+        }
+
+        Ok(Response::new(rpc::SubmitVisitsResponse {
+            game: Some(game.into_game().to_rpc()),
+        }))
+    }
+
+    async fn update_game_settings(
+        &self,
+        request: Request<rpc::UpdateGameSettingsRequest>,
+    ) -> Result<Response<rpc::Game>, Status> {
+        auth::authorize(&request, Role::Scorer)?;
+
+        let rpc::UpdateGameSettingsRequest {
+            game_id,
+            settings,
+            update_mask,
+            request_id,
+        } = request.into_inner();
+
+        let settings = settings.unwrap_or_default();
+        let fields = convert::update_mask(
+            update_mask,
+            &["players_number", "points_limit", "shot_clock"],
+        )?;
+
+        let players_number = fields
+            .contains(&"players_number")
+            .then(|| convert::number(settings.players_number, "settings.players_number"))
+            .transpose()?;
+        let points_limit = fields
+            .contains(&"points_limit")
+            .then(|| convert::points(settings.points_limit, "settings.points_limit"))
+            .transpose()?;
+        let shot_clock = fields
+            .contains(&"shot_clock")
+            .then(|| convert::shot_clock(settings.shot_clock, "settings.shot_clock"))
+            .transpose()?;
+
+        let game = coordinator::update_game_settings(coordinator::UpdateGameSettingsParameters {
+            game_id: game_id.try_convert()?,
+            games: &self.store,
+            players_number,
+            points_limit,
+            request_id: optional(&request_id),
+            shot_clock,
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
+
+        Ok(Response::new(game.to_rpc()))
+    }
+
+    async fn watch_game(
+        &self,
+        request: Request<rpc::WatchGameRequest>,
+    ) -> Result<Response<Self::WatchGameStream>, Status> {
+        auth::authorize(&request, Role::Viewer)?;
+
+        let rpc::WatchGameRequest { game_id } = request.into_inner();
+        let game_id = game_id.try_convert()?;
+
+        // Subscribe before reading the game so that no update is missed in between.
+        let mut updates = self.game_updates.subscribe();
+
+        let game = coordinator::get_game(coordinator::GetGameParameters {
+            games: &self.store,
+            game_id,
+        })
+        .await
+        .map_err(ToRpc::to_rpc)?;
+
+        let (sender, receiver) = mpsc::channel(1);
+        let store = self.store.clone();
+        let shutdown = self.shutdown.clone();
+
+        tokio::spawn(
+            async move {
+                if sender.send(Ok(game.to_rpc())).await.is_err() {
+                    return;
+                }
+
+                loop {
+                    let update = tokio::select! {
+                        _ = sender.closed() => break,
+                        // Ends the stream, so that shutdown does not wait for the watcher.
+                        _ = shutdown.requested() => break,
+                        update = updates.recv() => update,
+                    };
+
+                    match update {
+                        Ok(id) if id != game_id => continue,
+                        Ok(_) | Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => break,
+                    }
+
+                    let game = coordinator::get_game(coordinator::GetGameParameters {
+                        games: &store,
+                        game_id,
+                    })
+                    .await
+                    .map(ToRpc::to_rpc)
+                    .map_err(ToRpc::to_rpc);
+
+                    if sender.send(game).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
 }
 
 impl Server {
-    pub fn new(repo: Repo) -> Self {
-        Self { repo }
+    pub fn new(
+        store: Store,
+        game_updates: GameUpdates,
+        metrics: Arc<Metrics>,
+        shutdown: Shutdown,
+    ) -> Self {
+        Self {
+            game_updates,
+            metrics,
+            shutdown,
+            store,
+        }
+    }
+}
+
+/// Proto3 strings are empty when not set.
+fn optional(value: &str) -> Option<&str> {
+    (!value.is_empty()).then_some(value)
+}
+
+/// Deleted games are only listed to admins.
+fn list_role(include_deleted: bool) -> Role {
+    if include_deleted {
+        Role::Admin
+    } else {
+        Role::Viewer
     }
 }
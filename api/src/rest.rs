@@ -0,0 +1,839 @@
+//! HTTP JSON gateway to the Games service.
+//!
+//! Handlers delegate to the gRPC implementation, so both APIs share
+//! use cases, validation and error codes. The routes are described in `api/openapi.yaml`.
+
+use crate::{
+    auth::Tokens,
+    error_details,
+    playground::{rpc, rpc::games_server::Games, Server},
+};
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::DateTime;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tonic::{metadata::MetadataMap, Code, Extensions, Request, Status};
+
+const OPENAPI: &str = include_str!("../openapi.yaml");
+
+/// Header with the request id of mutating calls, see `request_id` in `games.proto`.
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+pub fn router(server: Arc<Server>, tokens: Tokens) -> Router {
+    Router::new()
+        .route("/openapi.yaml", get(openapi))
+        .route("/games", get(list_games).post(create_game))
+        .route(
+            "/games/:game_id",
+            get(get_game)
+                .delete(delete_game)
+                .patch(update_game_settings),
+        )
+        .route("/games/:game_id/abandon", post(abandon_game))
+        .route(
+            "/games/:game_id/corrections",
+            get(list_score_corrections).post(correct_score),
+        )
+        .route("/games/:game_id/cancel_last_score", post(cancel_last_score))
+        .route("/games/:game_id/points", post(count_points))
+        .route("/games/:game_id/timeline", get(get_game_timeline))
+        .route("/games/:game_id/visits", post(submit_visits))
+        .route("/game_summaries", get(list_game_summaries))
+        .with_state(RestState { server, tokens })
+}
+
+struct ApiError(Status);
+
+#[derive(Clone)]
+struct RestState {
+    server: Arc<Server>,
+    tokens: Tokens,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    code: String,
+    message: String,
+    reason: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    field_violations: Vec<FieldViolation>,
+}
+
+#[derive(Serialize)]
+struct FieldViolation {
+    field: String,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct AbandonGameBody {
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Default, Deserialize)]
+struct CreateGameBody {
+    shot_clock: Option<ShotClockBody>,
+}
+
+#[derive(Deserialize)]
+struct ListQuery {
+    #[serde(default)]
+    include_deleted: bool,
+}
+
+#[derive(Deserialize)]
+struct CorrectScoreBody {
+    round_number: i32,
+    player_number: i32,
+    points: i32,
+}
+
+#[derive(Deserialize)]
+struct CountPointsBody {
+    points: i32,
+    #[serde(default)]
+    expected_player_number: i32,
+    #[serde(default)]
+    expected_round_number: i32,
+}
+
+#[derive(Deserialize)]
+struct SubmitVisitsBody {
+    visits: Vec<VisitBody>,
+    #[serde(default)]
+    expected_player_number: i32,
+    #[serde(default)]
+    expected_round_number: i32,
+}
+
+#[derive(Deserialize)]
+struct VisitBody {
+    points: i32,
+}
+
+/// Settings present in the body are changed, as with an update mask.
+#[derive(Deserialize)]
+struct GameSettingsBody {
+    players_number: Option<i32>,
+    points_limit: Option<i32>,
+    /// `null` turns the shot clock off.
+    #[serde(default, deserialize_with = "present")]
+    shot_clock: Option<Option<ShotClockBody>>,
+}
+
+#[derive(Deserialize)]
+struct ShotClockBody {
+    turn_time_limit_seconds: f64,
+    #[serde(default)]
+    late_visit_policy: LateVisitPolicy,
+}
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LateVisitPolicy {
+    #[default]
+    Flag,
+    ScoreZero,
+}
+
+#[derive(Serialize)]
+struct Game {
+    id: String,
+    winner: String,
+    player: String,
+    player_points_to_win: i32,
+    create_time: Option<String>,
+    update_time: Option<String>,
+    rounds: Vec<Round>,
+    player_details: Vec<PlayerDetails>,
+    state: &'static str,
+    abandon_reason: String,
+    abandon_time: Option<String>,
+    players_number: i32,
+    points_limit: i32,
+    average_visit_seconds: Option<f64>,
+    duration_seconds: Option<f64>,
+    shot_clock: Option<ShotClock>,
+    turn_start_time: Option<String>,
+    remaining_turn_seconds: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct GameSummary {
+    id: String,
+    winner: String,
+    player: String,
+    player_points_to_win: i32,
+    create_time: Option<String>,
+    update_time: Option<String>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    players_number: i32,
+    points_limit: i32,
+    state: &'static str,
+    abandon_reason: String,
+    abandon_time: Option<String>,
+}
+
+#[derive(Serialize)]
+struct GameList {
+    games: Vec<Game>,
+}
+
+#[derive(Serialize)]
+struct GameSummaryList {
+    game_summaries: Vec<GameSummary>,
+}
+
+#[derive(Serialize)]
+struct ScoreCorrection {
+    round_number: i32,
+    player_number: i32,
+    previous_points: i32,
+    points: i32,
+    correct_time: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ScoreCorrectionList {
+    score_corrections: Vec<ScoreCorrection>,
+}
+
+#[derive(Serialize)]
+struct Timeline {
+    steps: Vec<TimelineStep>,
+}
+
+#[derive(Serialize)]
+struct ShotClock {
+    turn_time_limit_seconds: f64,
+    late_visit_policy: LateVisitPolicy,
+}
+
+#[derive(Serialize)]
+struct TimelineStep {
+    round_number: i32,
+    player_number: i32,
+    point: Option<Point>,
+    state: &'static str,
+    player: String,
+    winner: String,
+    player_details: Vec<PlayerDetails>,
+    time: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PlayerDetails {
+    name: String,
+    points_to_win: i32,
+}
+
+#[derive(Serialize)]
+struct Point {
+    value: i32,
+    kind: &'static str,
+    time: Option<String>,
+    late: bool,
+}
+
+#[derive(Serialize)]
+struct Round {
+    number: i32,
+    points: Vec<Point>,
+}
+
+async fn abandon_game(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+    Json(body): Json<AbandonGameBody>,
+) -> Result<Json<Game>, ApiError> {
+    let request_id = idempotency_key(&headers);
+    let game = state
+        .server
+        .abandon_game(state.request(
+            headers,
+            rpc::AbandonGameRequest {
+                game_id,
+                reason: body.reason,
+                request_id,
+            },
+        )?)
+        .await?
+        .into_inner();
+
+    Ok(Json(game.into()))
+}
+
+async fn cancel_last_score(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+) -> Result<Json<Game>, ApiError> {
+    let request_id = idempotency_key(&headers);
+    let response = state
+        .server
+        .cancel_last_score(state.request(
+            headers,
+            rpc::CancelLastScoreRequest {
+                game_id,
+                request_id,
+            },
+        )?)
+        .await?
+        .into_inner();
+
+    game_response(response.game)
+}
+
+async fn correct_score(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+    Json(body): Json<CorrectScoreBody>,
+) -> Result<Json<Game>, ApiError> {
+    let request_id = idempotency_key(&headers);
+    let response = state
+        .server
+        .correct_score(state.request(
+            headers,
+            rpc::CorrectScoreRequest {
+                game_id,
+                round_number: body.round_number,
+                player_number: body.player_number,
+                points: body.points,
+                request_id,
+            },
+        )?)
+        .await?
+        .into_inner();
+
+    game_response(response.game)
+}
+
+async fn count_points(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+    Json(body): Json<CountPointsBody>,
+) -> Result<Json<Game>, ApiError> {
+    let request_id = idempotency_key(&headers);
+    let response = state
+        .server
+        .count_points(state.request(
+            headers,
+            rpc::CountPointsRequest {
+                game_id,
+                points: body.points,
+                request_id,
+                expected_player_number: body.expected_player_number,
+                expected_round_number: body.expected_round_number,
+            },
+        )?)
+        .await?
+        .into_inner();
+
+    game_response(response.game)
+}
+
+/// The body is optional, a request without one creates a game without a shot clock.
+async fn create_game(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    body: Result<Json<CreateGameBody>, JsonRejection>,
+) -> Result<(StatusCode, Json<Game>), ApiError> {
+    let body = match body {
+        Ok(Json(body)) => body,
+        Err(JsonRejection::MissingJsonContentType(_)) => CreateGameBody::default(),
+        Err(rejection) => return Err(Status::invalid_argument(rejection.body_text()).into()),
+    };
+
+    let request_id = idempotency_key(&headers);
+    let message = rpc::CreateGameRequest {
+        request_id,
+        shot_clock: body.shot_clock.map(Into::into),
+    };
+    let game = state
+        .server
+        .create_game(state.request(headers, message)?)
+        .await?
+        .into_inner();
+
+    Ok((StatusCode::CREATED, Json(game.into())))
+}
+
+async fn delete_game(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let request_id = idempotency_key(&headers);
+    state
+        .server
+        .delete_game(state.request(
+            headers,
+            rpc::DeleteGameRequest {
+                game_id,
+                request_id,
+            },
+        )?)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_game(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+) -> Result<Json<Game>, ApiError> {
+    let game = state
+        .server
+        .get_game(state.request(headers, rpc::GetGameRequest { game_id })?)
+        .await?
+        .into_inner();
+
+    Ok(Json(game.into()))
+}
+
+async fn get_game_timeline(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+) -> Result<Json<Timeline>, ApiError> {
+    let response = state
+        .server
+        .get_game_timeline(state.request(headers, rpc::GetGameTimelineRequest { game_id })?)
+        .await?
+        .into_inner();
+
+    Ok(Json(Timeline {
+        steps: response.steps.into_iter().map(Into::into).collect(),
+    }))
+}
+
+async fn list_game_summaries(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<GameSummaryList>, ApiError> {
+    let message = rpc::ListGameSummariesRequest {
+        include_deleted: query.include_deleted,
+    };
+    let response = state
+        .server
+        .list_game_summaries(state.request(headers, message)?)
+        .await?
+        .into_inner();
+
+    Ok(Json(GameSummaryList {
+        game_summaries: response
+            .game_summaries
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    }))
+}
+
+async fn list_games(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<GameList>, ApiError> {
+    let message = rpc::ListGamesRequest {
+        include_deleted: query.include_deleted,
+    };
+    let response = state
+        .server
+        .list_games(state.request(headers, message)?)
+        .await?
+        .into_inner();
+
+    Ok(Json(GameList {
+        games: response.games.into_iter().map(Into::into).collect(),
+    }))
+}
+
+async fn list_score_corrections(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+) -> Result<Json<ScoreCorrectionList>, ApiError> {
+    let response = state
+        .server
+        .list_score_corrections(
+            state.request(headers, rpc::ListScoreCorrectionsRequest { game_id })?,
+        )
+        .await?
+        .into_inner();
+
+    Ok(Json(ScoreCorrectionList {
+        score_corrections: response
+            .score_corrections
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    }))
+}
+
+async fn submit_visits(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+    Json(body): Json<SubmitVisitsBody>,
+) -> Result<Json<Game>, ApiError> {
+    let request_id = idempotency_key(&headers);
+    let response = state
+        .server
+        .submit_visits(
+            state.request(
+                headers,
+                rpc::SubmitVisitsRequest {
+                    game_id,
+                    visits: body
+                        .visits
+                        .into_iter()
+                        .map(|visit| rpc::Visit {
+                            points: visit.points,
+                        })
+                        .collect(),
+                    request_id,
+                    expected_player_number: body.expected_player_number,
+                    expected_round_number: body.expected_round_number,
+                },
+            )?,
+        )
+        .await?
+        .into_inner();
+
+    game_response(response.game)
+}
+
+async fn update_game_settings(
+    State(state): State<RestState>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+    Json(body): Json<GameSettingsBody>,
+) -> Result<Json<Game>, ApiError> {
+    let mut paths = Vec::new();
+
+    if body.players_number.is_some() {
+        paths.push("players_number".to_string());
+    }
+
+    if body.points_limit.is_some() {
+        paths.push("points_limit".to_string());
+    }
+
+    if body.shot_clock.is_some() {
+        paths.push("shot_clock".to_string());
+    }
+
+    if paths.is_empty() {
+        return Err(Status::invalid_argument("No settings to change").into());
+    }
+
+    let request_id = idempotency_key(&headers);
+    let game = state
+        .server
+        .update_game_settings(state.request(
+            headers,
+            rpc::UpdateGameSettingsRequest {
+                game_id,
+                settings: Some(rpc::GameSettings {
+                    players_number: body.players_number.unwrap_or_default(),
+                    points_limit: body.points_limit.unwrap_or_default(),
+                    shot_clock: body.shot_clock.flatten().map(Into::into),
+                }),
+                update_mask: Some(prost_types::FieldMask { paths }),
+                request_id,
+            },
+        )?)
+        .await?
+        .into_inner();
+
+    Ok(Json(game.into()))
+}
+
+async fn openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/yaml")], OPENAPI)
+}
+
+fn game_response(game: Option<rpc::Game>) -> Result<Json<Game>, ApiError> {
+    let game = game.ok_or(Status::internal("Missing game in response"))?;
+
+    Ok(Json(game.into()))
+}
+
+/// Empty, like an unset proto3 string, when the header is missing.
+fn idempotency_key(headers: &HeaderMap) -> String {
+    headers
+        .get(IDEMPOTENCY_KEY)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn game_state(state: rpc::GameState) -> &'static str {
+    match state {
+        rpc::GameState::Unset => "unset",
+        rpc::GameState::NotStarted => "not_started",
+        rpc::GameState::InProgress => "in_progress",
+        rpc::GameState::Finished => "finished",
+        rpc::GameState::Abandoned => "abandoned",
+    }
+}
+
+fn timestamp(timestamp: prost_types::Timestamp) -> Option<String> {
+    DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32)
+        .map(|time| time.to_rfc3339())
+}
+
+fn seconds(duration: prost_types::Duration) -> f64 {
+    duration.seconds as f64 + f64::from(duration.nanos) / 1e9
+}
+
+fn duration(seconds: f64) -> prost_types::Duration {
+    prost_types::Duration {
+        seconds: seconds.trunc() as i64,
+        nanos: (seconds.fract() * 1e9) as i32,
+    }
+}
+
+/// Tells a field set to `null` from a missing one, which stays `None` by default.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+impl RestState {
+    /// Builds the gRPC request, authenticated the same way the interceptor does.
+    fn request<T>(&self, headers: HeaderMap, message: T) -> Result<Request<T>, ApiError> {
+        let request = Request::from_parts(
+            MetadataMap::from_headers(headers),
+            Extensions::default(),
+            message,
+        );
+
+        Ok(self.tokens.authenticate(request)?)
+    }
+}
+
+impl From<Status> for ApiError {
+    fn from(status: Status) -> Self {
+        Self(status)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.0;
+
+        // Mapping recommended by google.rpc.Code documentation.
+        let status_code = match status.code() {
+            Code::Ok => StatusCode::OK,
+            Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+                StatusCode::BAD_REQUEST
+            }
+            Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+            Code::PermissionDenied => StatusCode::FORBIDDEN,
+            Code::NotFound => StatusCode::NOT_FOUND,
+            Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+            Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+            Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+            Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+            Code::Cancelled | Code::Unknown | Code::Internal | Code::DataLoss => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+
+        let mut body = ErrorBody {
+            code: format!("{:?}", status.code()),
+            message: status.message().to_string(),
+            reason: None,
+            field_violations: Vec::new(),
+        };
+
+        let details = error_details::rpc::Status::decode(status.details()).unwrap_or_default();
+
+        for detail in details.details {
+            match detail.type_url.rsplit('.').next() {
+                Some("ErrorInfo") => {
+                    body.reason = error_details::rpc::ErrorInfo::decode(detail.value.as_slice())
+                        .ok()
+                        .map(|info| info.reason);
+                }
+                Some("BadRequest") => {
+                    let bad_request =
+                        error_details::rpc::BadRequest::decode(detail.value.as_slice())
+                            .unwrap_or_default();
+
+                    body.field_violations = bad_request
+                        .field_violations
+                        .into_iter()
+                        .map(|violation| FieldViolation {
+                            field: violation.field,
+                            description: violation.description,
+                        })
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+
+        (status_code, Json(body)).into_response()
+    }
+}
+
+impl From<rpc::Game> for Game {
+    fn from(game: rpc::Game) -> Self {
+        let state = game_state(game.state());
+
+        Self {
+            id: game.id,
+            winner: game.winner,
+            player: game.player,
+            player_points_to_win: game.player_points_to_win,
+            create_time: game.create_time.and_then(timestamp),
+            update_time: game.update_time.and_then(timestamp),
+            rounds: game.rounds.into_iter().map(Into::into).collect(),
+            player_details: game.player_details.into_iter().map(Into::into).collect(),
+            state,
+            abandon_reason: game.abandon_reason,
+            abandon_time: game.abandon_time.and_then(timestamp),
+            players_number: game.players_number,
+            points_limit: game.points_limit,
+            average_visit_seconds: game.average_visit_duration.map(seconds),
+            duration_seconds: game.duration.map(seconds),
+            shot_clock: game.shot_clock.map(Into::into),
+            turn_start_time: game.turn_start_time.and_then(timestamp),
+            remaining_turn_seconds: game.remaining_turn_time.map(seconds),
+        }
+    }
+}
+
+impl From<rpc::GameSummary> for GameSummary {
+    fn from(summary: rpc::GameSummary) -> Self {
+        let state = game_state(summary.state());
+
+        Self {
+            id: summary.id,
+            winner: summary.winner,
+            player: summary.player,
+            player_points_to_win: summary.player_points_to_win,
+            create_time: summary.create_time.and_then(timestamp),
+            update_time: summary.update_time.and_then(timestamp),
+            start_time: summary.start_time.and_then(timestamp),
+            end_time: summary.end_time.and_then(timestamp),
+            players_number: summary.players_number,
+            points_limit: summary.points_limit,
+            state,
+            abandon_reason: summary.abandon_reason,
+            abandon_time: summary.abandon_time.and_then(timestamp),
+        }
+    }
+}
+
+impl From<rpc::ScoreCorrection> for ScoreCorrection {
+    fn from(correction: rpc::ScoreCorrection) -> Self {
+        Self {
+            round_number: correction.round_number,
+            player_number: correction.player_number,
+            previous_points: correction.previous_points,
+            points: correction.points,
+            correct_time: correction.correct_time.and_then(timestamp),
+        }
+    }
+}
+
+impl From<rpc::TimelineStep> for TimelineStep {
+    fn from(step: rpc::TimelineStep) -> Self {
+        let state = game_state(step.state());
+
+        Self {
+            round_number: step.round_number,
+            player_number: step.player_number,
+            point: step.point.map(Into::into),
+            state,
+            player: step.player,
+            winner: step.winner,
+            player_details: step.player_details.into_iter().map(Into::into).collect(),
+            time: step.time.and_then(timestamp),
+        }
+    }
+}
+
+impl From<rpc::PlayerDetails> for PlayerDetails {
+    fn from(details: rpc::PlayerDetails) -> Self {
+        Self {
+            name: details.name,
+            points_to_win: details.points_to_win,
+        }
+    }
+}
+
+impl From<rpc::Point> for Point {
+    fn from(point: rpc::Point) -> Self {
+        let kind = match point.kind() {
+            rpc::PointKind::Unset => "unset",
+            rpc::PointKind::Regular => "regular",
+            rpc::PointKind::Overthrow => "overthrow",
+        };
+
+        Self {
+            value: point.value,
+            kind,
+            time: point.time.and_then(timestamp),
+            late: point.late,
+        }
+    }
+}
+
+impl From<rpc::ShotClock> for ShotClock {
+    fn from(shot_clock: rpc::ShotClock) -> Self {
+        let late_visit_policy = match shot_clock.late_visit_policy() {
+            rpc::LateVisitPolicy::Unset | rpc::LateVisitPolicy::Flag => LateVisitPolicy::Flag,
+            rpc::LateVisitPolicy::ScoreZero => LateVisitPolicy::ScoreZero,
+        };
+
+        Self {
+            turn_time_limit_seconds: shot_clock.turn_time_limit.map(seconds).unwrap_or_default(),
+            late_visit_policy,
+        }
+    }
+}
+
+impl From<ShotClockBody> for rpc::ShotClock {
+    fn from(body: ShotClockBody) -> Self {
+        let late_visit_policy = match body.late_visit_policy {
+            LateVisitPolicy::Flag => rpc::LateVisitPolicy::Flag,
+            LateVisitPolicy::ScoreZero => rpc::LateVisitPolicy::ScoreZero,
+        };
+
+        Self {
+            turn_time_limit: Some(duration(body.turn_time_limit_seconds)),
+            late_visit_policy: late_visit_policy.into(),
+        }
+    }
+}
+
+impl From<rpc::Round> for Round {
+    fn from(round: rpc::Round) -> Self {
+        Self {
+            number: round.number,
+            points: round.points.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Resolves for every holder once the process received SIGINT or SIGTERM.
+#[derive(Clone)]
+pub struct Shutdown {
+    receiver: watch::Receiver<bool>,
+}
+
+impl Shutdown {
+    pub fn listen() -> Self {
+        let (sender, receiver) = watch::channel(false);
+
+        tokio::spawn(async move {
+            signal().await;
+            tracing::info!("Shutting down, waiting for in-flight requests");
+            let _ = sender.send(true);
+        });
+
+        Self { receiver }
+    }
+
+    pub async fn requested(&self) {
+        let mut receiver = self.receiver.clone();
+
+        // The sender is only dropped after sending, so an error also means shutdown.
+        let _ = receiver.wait_for(|requested| *requested).await;
+    }
+
+    /// Resolves when requests were given `timeout` to finish after shutdown was requested.
+    pub async fn drain_elapsed(&self, timeout: Duration) {
+        self.requested().await;
+        tokio::time::sleep(timeout).await;
+    }
+}
+
+#[cfg(unix)]
+async fn signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate()).expect("SIGTERM handler");
+
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {},
+        _ = terminate.recv() => {},
+    }
+}
+
+#[cfg(not(unix))]
+async fn signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -0,0 +1,354 @@
+//! Storage of games behind the Games service.
+//!
+//! The api keeps games in Postgres, the mock keeps them in memory so frontends can
+//! develop against the same service without a database.
+
+use crate::game_updates::GameUpdates;
+use chrono::Utc;
+use dataspine::{PoolStatus, Repo};
+use playground::{coordinator, Error, Game, GameSummary, Reason, ScoreCorrection};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+use uuid::Uuid;
+
+/// Same page size as the database listings.
+const LIST_LIMIT: usize = 10;
+
+#[derive(Clone)]
+pub enum Store {
+    Memory(MemoryStore),
+    Postgres(Repo),
+}
+
+/// Games of a single process, lost on restart.
+#[derive(Clone)]
+pub struct MemoryStore {
+    corrections: Arc<RwLock<HashMap<Uuid, Vec<ScoreCorrection>>>>,
+    /// Ids of deleted games, which are kept in `games` as the database keeps their rows.
+    deleted: Arc<RwLock<HashSet<Uuid>>>,
+    game_updates: GameUpdates,
+    games: Arc<RwLock<HashMap<Uuid, Game>>>,
+    /// Games as each request left them, by request id.
+    requests: Arc<RwLock<HashMap<String, Game>>>,
+}
+
+impl Store {
+    /// Database pool usage, `None` for stores without a database.
+    pub fn pool_status(&self) -> Option<PoolStatus> {
+        match self {
+            Store::Memory(_) => None,
+            Store::Postgres(repo) => Some(repo.pool_status()),
+        }
+    }
+}
+
+impl coordinator::CorrectGame for Store {
+    async fn correct_game(
+        &self,
+        game: &mut Game,
+        correction: &ScoreCorrection,
+        request_id: Option<&str>,
+    ) -> Result<(), Error> {
+        match self {
+            Store::Memory(store) => store.correct_game(game, correction, request_id).await,
+            Store::Postgres(repo) => repo.correct_game(game, correction, request_id).await,
+        }
+    }
+}
+
+impl coordinator::DeleteGame for Store {
+    async fn delete_game(&self, id: Uuid, request_id: Option<&str>) -> Result<(), Error> {
+        match self {
+            Store::Memory(store) => store.delete_game(id, request_id).await,
+            Store::Postgres(repo) => repo.delete_game(id, request_id).await,
+        }
+    }
+}
+
+impl coordinator::GetGame for Store {
+    async fn get_game(&self, id: Uuid) -> Result<Game, Error> {
+        match self {
+            Store::Memory(store) => store.get_game(id).await,
+            Store::Postgres(repo) => repo.get_game(id).await,
+        }
+    }
+}
+
+impl coordinator::GetRequestedGame for Store {
+    async fn get_requested_game(
+        &self,
+        request_id: &str,
+        game_id: Option<Uuid>,
+    ) -> Result<Option<Game>, Error> {
+        match self {
+            Store::Memory(store) => store.get_requested_game(request_id, game_id).await,
+            Store::Postgres(repo) => repo.get_requested_game(request_id, game_id).await,
+        }
+    }
+}
+
+impl coordinator::InsertGame for Store {
+    async fn insert_game(&self, game: &mut Game, request_id: Option<&str>) -> Result<(), Error> {
+        match self {
+            Store::Memory(store) => store.insert_game(game, request_id).await,
+            Store::Postgres(repo) => repo.insert_game(game, request_id).await,
+        }
+    }
+}
+
+impl coordinator::ListGames for Store {
+    async fn list_games(&self, include_deleted: bool) -> Result<Vec<Game>, Error> {
+        match self {
+            Store::Memory(store) => store.list_games(include_deleted).await,
+            Store::Postgres(repo) => repo.list_games(include_deleted).await,
+        }
+    }
+}
+
+impl coordinator::ListGameSummaries for Store {
+    async fn list_game_summaries(&self, include_deleted: bool) -> Result<Vec<GameSummary>, Error> {
+        match self {
+            Store::Memory(store) => store.list_game_summaries(include_deleted).await,
+            Store::Postgres(repo) => repo.list_game_summaries(include_deleted).await,
+        }
+    }
+}
+
+impl coordinator::ListScoreCorrections for Store {
+    async fn list_score_corrections(&self, game_id: Uuid) -> Result<Vec<ScoreCorrection>, Error> {
+        match self {
+            Store::Memory(store) => store.list_score_corrections(game_id).await,
+            Store::Postgres(repo) => repo.list_score_corrections(game_id).await,
+        }
+    }
+}
+
+impl coordinator::UpdateGame for Store {
+    async fn update_game(&self, game: &mut Game, request_id: Option<&str>) -> Result<(), Error> {
+        match self {
+            Store::Memory(store) => store.update_game(game, request_id).await,
+            Store::Postgres(repo) => repo.update_game(game, request_id).await,
+        }
+    }
+}
+
+impl MemoryStore {
+    /// Changes are published to `game_updates`, as database notifications are for the api.
+    pub fn new(game_updates: GameUpdates) -> Self {
+        Self {
+            corrections: Arc::default(),
+            deleted: Arc::default(),
+            game_updates,
+            games: Arc::default(),
+            requests: Arc::default(),
+        }
+    }
+
+    /// Adds saved games, replacing games with the same ids.
+    pub fn seed(&self, games: Vec<Game>) {
+        let mut stored = self.games.write().unwrap();
+
+        for game in games {
+            if let Some(id) = game.id() {
+                stored.insert(id, game);
+            }
+        }
+    }
+
+    /// Saves the game into `games`, locked by the caller, and the request that changed it.
+    fn save(
+        &self,
+        games: &mut HashMap<Uuid, Game>,
+        id: Uuid,
+        game: &Game,
+        request_id: Option<&str>,
+    ) -> Result<(), Error> {
+        if let Some(request_id) = request_id {
+            self.record_request(game, request_id)?;
+        }
+
+        games.insert(id, game.clone());
+
+        Ok(())
+    }
+
+    fn record_request(&self, game: &Game, request_id: &str) -> Result<(), Error> {
+        let mut requests = self.requests.write().unwrap();
+
+        if requests.contains_key(request_id) {
+            return Err(Error::AlreadyExists(
+                Reason::DuplicateRequestId,
+                format!("Request {request_id}"),
+            ));
+        }
+
+        requests.insert(request_id.to_string(), game.clone());
+
+        Ok(())
+    }
+
+    /// Game that is not deleted, from `games` locked by the caller.
+    fn find<'a>(&self, games: &'a HashMap<Uuid, Game>, id: Uuid) -> Result<&'a Game, Error> {
+        games
+            .get(&id)
+            .filter(|_| !self.deleted.read().unwrap().contains(&id))
+            .ok_or(Error::NotFound(Reason::GameNotFound, format!("Game {id}")))
+    }
+
+    /// Newest games first, deleted ones left out.
+    fn latest_games(&self, include_deleted: bool) -> Vec<Game> {
+        let stored = self.games.read().unwrap();
+        let deleted = self.deleted.read().unwrap();
+        let mut games: Vec<Game> = stored
+            .values()
+            .filter(|game| include_deleted || game.id().is_some_and(|id| !deleted.contains(&id)))
+            .cloned()
+            .collect();
+
+        games.sort_by_key(|game| Reverse(game.create_time()));
+        games.truncate(LIST_LIMIT);
+
+        games
+    }
+}
+
+impl coordinator::CorrectGame for MemoryStore {
+    async fn correct_game(
+        &self,
+        game: &mut Game,
+        correction: &ScoreCorrection,
+        request_id: Option<&str>,
+    ) -> Result<(), Error> {
+        coordinator::UpdateGame::update_game(self, game, request_id).await?;
+
+        if let Some(id) = game.id() {
+            self.corrections
+                .write()
+                .unwrap()
+                .entry(id)
+                .or_default()
+                .push(correction.clone());
+        }
+
+        Ok(())
+    }
+}
+
+impl coordinator::DeleteGame for MemoryStore {
+    async fn delete_game(&self, id: Uuid, request_id: Option<&str>) -> Result<(), Error> {
+        let games = self.games.write().unwrap();
+        let game = self.find(&games, id)?;
+
+        if let Some(request_id) = request_id {
+            self.record_request(game, request_id)?;
+        }
+
+        self.deleted.write().unwrap().insert(id);
+        drop(games);
+
+        self.game_updates.publish(id);
+
+        Ok(())
+    }
+}
+
+impl coordinator::GetGame for MemoryStore {
+    async fn get_game(&self, id: Uuid) -> Result<Game, Error> {
+        self.find(&self.games.read().unwrap(), id).cloned()
+    }
+}
+
+impl coordinator::GetRequestedGame for MemoryStore {
+    async fn get_requested_game(
+        &self,
+        request_id: &str,
+        game_id: Option<Uuid>,
+    ) -> Result<Option<Game>, Error> {
+        let game = self
+            .requests
+            .read()
+            .unwrap()
+            .get(request_id)
+            .filter(|game| game_id.is_none() || game.id() == game_id)
+            .cloned();
+
+        Ok(game)
+    }
+}
+
+impl coordinator::InsertGame for MemoryStore {
+    async fn insert_game(&self, game: &mut Game, request_id: Option<&str>) -> Result<(), Error> {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+
+        game.assign_id(id)?;
+        game.assign_create_time(now)?;
+        game.change_update_time(now)?;
+
+        self.save(&mut self.games.write().unwrap(), id, game, request_id)?;
+        self.game_updates.publish(id);
+
+        Ok(())
+    }
+}
+
+impl coordinator::ListGames for MemoryStore {
+    async fn list_games(&self, include_deleted: bool) -> Result<Vec<Game>, Error> {
+        Ok(self.latest_games(include_deleted))
+    }
+}
+
+impl coordinator::ListGameSummaries for MemoryStore {
+    async fn list_game_summaries(&self, include_deleted: bool) -> Result<Vec<GameSummary>, Error> {
+        self.latest_games(include_deleted)
+            .iter()
+            .map(TryInto::try_into)
+            .collect()
+    }
+}
+
+impl coordinator::ListScoreCorrections for MemoryStore {
+    async fn list_score_corrections(&self, game_id: Uuid) -> Result<Vec<ScoreCorrection>, Error> {
+        let corrections = self
+            .corrections
+            .read()
+            .unwrap()
+            .get(&game_id)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(corrections)
+    }
+}
+
+impl coordinator::UpdateGame for MemoryStore {
+    async fn update_game(&self, game: &mut Game, request_id: Option<&str>) -> Result<(), Error> {
+        let id = game
+            .id()
+            .ok_or(eyre::eyre!("Trying to update game without id"))?;
+
+        // One guard for the check and the save, so a game deleted meanwhile stays deleted.
+        let mut games = self.games.write().unwrap();
+
+        let stored = self.find(&games, id)?;
+
+        if stored.update_time() != game.update_time() {
+            return Err(Error::Aborted(
+                Reason::GameChanged,
+                format!("Game {id} was changed since it was read"),
+            ));
+        }
+
+        game.change_update_time(Utc::now())?;
+
+        self.save(&mut games, id, game, request_id)?;
+        drop(games);
+
+        self.game_updates.publish(id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use axum::http::Request;
+use tower_http::request_id::RequestId;
+use tracing::Span;
+
+/// Span wrapping a whole gRPC or HTTP request, so that everything logged while serving
+/// it carries the request id, which is also returned in the `x-request-id` header.
+pub fn make_span<B>(request: &Request<B>) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .and_then(|id| id.header_value().to_str().ok())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        path = %request.uri().path(),
+        request_id,
+    )
+}
@@ -0,0 +1,59 @@
+use api::{
+    game_updates::GameUpdates,
+    metrics::Metrics,
+    playground::{
+        rpc::{games_client::GamesClient, games_server::GamesServer},
+        Server,
+    },
+    shutdown::Shutdown,
+    store::{MemoryStore, Store},
+};
+use dataspine::{Repo, Role};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{service::interceptor::InterceptedService, transport::Channel, Request, Status};
+
+pub type Client = GamesClient<Channel>;
+
+/// Games service as served by `api_mock`.
+pub async fn memory() -> anyhow::Result<Client> {
+    let game_updates = GameUpdates::default();
+    let store = Store::Memory(MemoryStore::new(game_updates.clone()));
+
+    serve(store, game_updates).await
+}
+
+/// Games service as served by `api`.
+pub async fn postgres(pool: PgPool) -> anyhow::Result<Client> {
+    // Without a database listener, which would hold a connection after the test ends.
+    serve(Store::Postgres(Repo::new(pool)), GameUpdates::default()).await
+}
+
+async fn serve(store: Store, game_updates: GameUpdates) -> anyhow::Result<Client> {
+    let metrics = Arc::new(Metrics::new(store.clone())?);
+    let server = Server::new(store, game_updates, metrics, Shutdown::listen());
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(InterceptedService::new(
+                GamesServer::new(server),
+                authenticate,
+            ))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let client = GamesClient::connect(format!("http://{address}")).await?;
+
+    Ok(client)
+}
+
+/// Roles are not part of the contract, every scenario may call everything.
+fn authenticate(mut request: Request<()>) -> Result<Request<()>, Status> {
+    request.extensions_mut().insert(Role::Admin);
+
+    Ok(request)
+}
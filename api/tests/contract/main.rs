@@ -0,0 +1,7 @@
+//! Scenarios every Games service implementation has to pass, run over gRPC against the
+//! api on a test database and against the in-memory store served by `api_mock`.
+
+pub mod harness;
+pub mod memory_test;
+pub mod postgres_test;
+pub mod scenarios;
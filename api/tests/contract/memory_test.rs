@@ -0,0 +1,146 @@
+use crate::{harness, scenarios};
+
+#[tokio::test]
+async fn it_creates_game() -> anyhow::Result<()> {
+    scenarios::it_creates_game(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_counts_points() -> anyhow::Result<()> {
+    scenarios::it_counts_points(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_counts_points_once_per_request_id() -> anyhow::Result<()> {
+    scenarios::it_counts_points_once_per_request_id(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_creates_game_once_per_request_id() -> anyhow::Result<()> {
+    scenarios::it_creates_game_once_per_request_id(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_creates_game_with_shot_clock() -> anyhow::Result<()> {
+    scenarios::it_creates_game_with_shot_clock(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_counts_overthrow() -> anyhow::Result<()> {
+    scenarios::it_counts_overthrow(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_finishes_game() -> anyhow::Result<()> {
+    scenarios::it_finishes_game(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_count_points_over_limit() -> anyhow::Result<()> {
+    scenarios::it_does_not_count_points_over_limit(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_submits_visits() -> anyhow::Result<()> {
+    scenarios::it_submits_visits(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_submit_visits_after_rejected_one() -> anyhow::Result<()> {
+    scenarios::it_does_not_submit_visits_after_rejected_one(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_cancels_last_score() -> anyhow::Result<()> {
+    scenarios::it_cancels_last_score(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_cancel_score_of_not_started_game() -> anyhow::Result<()> {
+    scenarios::it_does_not_cancel_score_of_not_started_game(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_count_points_of_past_turn() -> anyhow::Result<()> {
+    scenarios::it_does_not_count_points_of_past_turn(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_get_unknown_game() -> anyhow::Result<()> {
+    scenarios::it_does_not_get_unknown_game(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_get_game_with_invalid_id() -> anyhow::Result<()> {
+    scenarios::it_does_not_get_game_with_invalid_id(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_abandons_game() -> anyhow::Result<()> {
+    scenarios::it_abandons_game(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_deletes_game() -> anyhow::Result<()> {
+    scenarios::it_deletes_game(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_deletes_game_once_per_request_id() -> anyhow::Result<()> {
+    scenarios::it_deletes_game_once_per_request_id(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_lists_deleted_games_on_request() -> anyhow::Result<()> {
+    scenarios::it_lists_deleted_games_on_request(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_updates_game_settings() -> anyhow::Result<()> {
+    scenarios::it_updates_game_settings(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_update_players_number_over_limit() -> anyhow::Result<()> {
+    scenarios::it_does_not_update_players_number_over_limit(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_update_unknown_game_setting() -> anyhow::Result<()> {
+    scenarios::it_does_not_update_unknown_game_setting(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_scores_late_visit_zero() -> anyhow::Result<()> {
+    scenarios::it_scores_late_visit_zero(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_time_submitted_visits() -> anyhow::Result<()> {
+    scenarios::it_does_not_time_submitted_visits(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_set_shot_clock_over_an_hour() -> anyhow::Result<()> {
+    scenarios::it_does_not_set_shot_clock_over_an_hour(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_corrects_score() -> anyhow::Result<()> {
+    scenarios::it_corrects_score(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_does_not_correct_score_finishing_game_early() -> anyhow::Result<()> {
+    scenarios::it_does_not_correct_score_finishing_game_early(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_gets_game_timeline() -> anyhow::Result<()> {
+    scenarios::it_gets_game_timeline(harness::memory().await?).await
+}
+
+#[tokio::test]
+async fn it_gets_game_pace() -> anyhow::Result<()> {
+    scenarios::it_gets_game_pace(harness::memory().await?).await
+}
@@ -0,0 +1,147 @@
+use crate::{harness, scenarios};
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_creates_game(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_creates_game(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_counts_points(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_counts_points(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_counts_points_once_per_request_id(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_counts_points_once_per_request_id(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_creates_game_once_per_request_id(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_creates_game_once_per_request_id(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_creates_game_with_shot_clock(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_creates_game_with_shot_clock(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_counts_overthrow(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_counts_overthrow(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_finishes_game(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_finishes_game(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_count_points_over_limit(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_count_points_over_limit(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_submits_visits(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_submits_visits(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_submit_visits_after_rejected_one(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_submit_visits_after_rejected_one(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_cancels_last_score(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_cancels_last_score(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_cancel_score_of_not_started_game(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_cancel_score_of_not_started_game(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_count_points_of_past_turn(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_count_points_of_past_turn(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_get_unknown_game(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_get_unknown_game(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_get_game_with_invalid_id(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_get_game_with_invalid_id(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_abandons_game(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_abandons_game(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_deletes_game(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_deletes_game(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_deletes_game_once_per_request_id(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_deletes_game_once_per_request_id(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_lists_deleted_games_on_request(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_lists_deleted_games_on_request(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_updates_game_settings(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_updates_game_settings(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_update_players_number_over_limit(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_update_players_number_over_limit(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_update_unknown_game_setting(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_update_unknown_game_setting(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_scores_late_visit_zero(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_scores_late_visit_zero(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_time_submitted_visits(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_time_submitted_visits(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_set_shot_clock_over_an_hour(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_set_shot_clock_over_an_hour(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_corrects_score(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_corrects_score(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_does_not_correct_score_finishing_game_early(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_does_not_correct_score_finishing_game_early(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_gets_game_timeline(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_gets_game_timeline(harness::postgres(pool).await?).await
+}
+
+#[sqlx::test(migrations = "../dataspine/migrations")]
+async fn it_gets_game_pace(pool: PgPool) -> anyhow::Result<()> {
+    scenarios::it_gets_game_pace(harness::postgres(pool).await?).await
+}
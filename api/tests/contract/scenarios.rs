@@ -0,0 +1,788 @@
+use crate::harness::Client;
+use api::playground::rpc;
+use tonic::Code;
+use uuid::Uuid;
+
+pub async fn it_creates_game(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    assert!(Uuid::parse_str(&game.id).is_ok());
+    assert_eq!(game.player, "Player1");
+    assert_eq!(game.player_points_to_win, 301);
+    assert!(game.rounds.is_empty());
+    assert!(game.winner.is_empty());
+
+    let found = client
+        .get_game(rpc::GetGameRequest {
+            game_id: game.id.clone(),
+        })
+        .await?
+        .into_inner();
+
+    assert_eq!(found.id, game.id);
+
+    Ok(())
+}
+
+pub async fn it_counts_points(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let game = count_points(&mut client, &game.id, &[60]).await?;
+
+    assert_eq!(game.player, "Player2");
+    assert_eq!(game.player_details[0].points_to_win, 241);
+    assert_eq!(game.rounds[0].points[0].value, 60);
+    assert_eq!(game.rounds[0].points[0].kind(), rpc::PointKind::Regular);
+
+    Ok(())
+}
+
+pub async fn it_counts_points_once_per_request_id(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+    let request = rpc::CountPointsRequest {
+        game_id: game.id.clone(),
+        points: 60,
+        request_id: "first-visit".to_string(),
+        ..Default::default()
+    };
+
+    let counted = client.count_points(request.clone()).await?.into_inner();
+    count_points(&mut client, &game.id, &[45]).await?;
+    let replayed = client.count_points(request).await?.into_inner();
+
+    assert_eq!(replayed, counted);
+
+    let game = client
+        .get_game(rpc::GetGameRequest { game_id: game.id })
+        .await?
+        .into_inner();
+
+    assert_eq!(game.player_details[0].points_to_win, 241);
+    assert_eq!(game.player_details[1].points_to_win, 256);
+
+    Ok(())
+}
+
+pub async fn it_does_not_count_points_of_past_turn(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+    count_points(&mut client, &game.id, &[60]).await?;
+
+    let result = client
+        .count_points(rpc::CountPointsRequest {
+            game_id: game.id.clone(),
+            points: 45,
+            expected_player_number: 1,
+            expected_round_number: 1,
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::FailedPrecondition));
+
+    let game = client
+        .count_points(rpc::CountPointsRequest {
+            game_id: game.id,
+            points: 45,
+            expected_player_number: 2,
+            expected_round_number: 1,
+            ..Default::default()
+        })
+        .await?
+        .into_inner()
+        .game
+        .unwrap_or_default();
+
+    assert_eq!(game.player_details[1].points_to_win, 256);
+
+    Ok(())
+}
+
+pub async fn it_creates_game_once_per_request_id(mut client: Client) -> anyhow::Result<()> {
+    let request = rpc::CreateGameRequest {
+        request_id: "first-game".to_string(),
+        ..Default::default()
+    };
+
+    let created = client.create_game(request.clone()).await?.into_inner();
+    let replayed = client.create_game(request).await?.into_inner();
+
+    assert_eq!(replayed, created);
+
+    Ok(())
+}
+
+pub async fn it_creates_game_with_shot_clock(mut client: Client) -> anyhow::Result<()> {
+    let turn_time_limit = prost_types::Duration {
+        seconds: 30,
+        nanos: 0,
+    };
+
+    let game = client
+        .create_game(rpc::CreateGameRequest {
+            shot_clock: Some(rpc::ShotClock {
+                turn_time_limit: Some(turn_time_limit.clone()),
+                late_visit_policy: rpc::LateVisitPolicy::ScoreZero.into(),
+            }),
+            ..Default::default()
+        })
+        .await?
+        .into_inner();
+
+    let shot_clock = game.shot_clock.unwrap_or_default();
+
+    assert_eq!(shot_clock.turn_time_limit, Some(turn_time_limit));
+    assert_eq!(
+        shot_clock.late_visit_policy(),
+        rpc::LateVisitPolicy::ScoreZero
+    );
+
+    let result = client
+        .create_game(rpc::CreateGameRequest {
+            shot_clock: Some(rpc::ShotClock::default()),
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::InvalidArgument));
+
+    Ok(())
+}
+
+pub async fn it_counts_overthrow(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let game = count_points(&mut client, &game.id, &[180, 0, 180]).await?;
+
+    assert_eq!(game.player, "Player2");
+    assert_eq!(game.player_details[0].points_to_win, 121);
+    let round = game.rounds.iter().find(|round| round.number == 2).unwrap();
+
+    assert_eq!(round.points[0].kind(), rpc::PointKind::Overthrow);
+
+    Ok(())
+}
+
+pub async fn it_finishes_game(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let game = count_points(&mut client, &game.id, &[180, 0, 121]).await?;
+
+    assert_eq!(game.winner, "Player1");
+    assert!(game.player.is_empty());
+
+    let result = count_points(&mut client, &game.id, &[60]).await;
+
+    assert_eq!(code(result), Some(Code::FailedPrecondition));
+
+    Ok(())
+}
+
+pub async fn it_does_not_count_points_over_limit(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let result = count_points(&mut client, &game.id, &[181]).await;
+
+    assert_eq!(code(result), Some(Code::InvalidArgument));
+
+    Ok(())
+}
+
+pub async fn it_submits_visits(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let game = client
+        .submit_visits(submit_visits_request(&game.id, &[180, 0, 121]))
+        .await?
+        .into_inner()
+        .game
+        .unwrap_or_default();
+
+    assert_eq!(game.winner, "Player1");
+
+    Ok(())
+}
+
+pub async fn it_does_not_submit_visits_after_rejected_one(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let finished = client
+        .submit_visits(submit_visits_request(&game.id, &[180, 0, 121, 60]))
+        .await;
+    let over_limit = client
+        .submit_visits(submit_visits_request(&game.id, &[60, 181]))
+        .await;
+
+    let status = finished.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert!(status.message().starts_with("Visit 3:"));
+
+    let status = over_limit.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert!(status.message().starts_with("Visit 1:"));
+
+    let game = client
+        .get_game(rpc::GetGameRequest { game_id: game.id })
+        .await?
+        .into_inner();
+
+    assert!(game.rounds.is_empty());
+
+    Ok(())
+}
+
+pub async fn it_abandons_game(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+    count_points(&mut client, &game.id, &[60]).await?;
+
+    let game = client
+        .abandon_game(rpc::AbandonGameRequest {
+            game_id: game.id,
+            reason: "Power cut".to_string(),
+            ..Default::default()
+        })
+        .await?
+        .into_inner();
+
+    assert_eq!(game.state(), rpc::GameState::Abandoned);
+    assert_eq!(game.abandon_reason, "Power cut");
+    assert!(game.abandon_time.is_some());
+    assert!(game.player.is_empty());
+    assert_eq!(game.rounds.len(), 1);
+
+    let result = count_points(&mut client, &game.id, &[45]).await;
+
+    assert_eq!(code(result), Some(Code::FailedPrecondition));
+
+    let summaries = client
+        .list_game_summaries(rpc::ListGameSummariesRequest::default())
+        .await?
+        .into_inner()
+        .game_summaries;
+
+    assert_eq!(summaries[0].state(), rpc::GameState::Abandoned);
+
+    Ok(())
+}
+
+pub async fn it_deletes_game(mut client: Client) -> anyhow::Result<()> {
+    let kept = create_game(&mut client).await?;
+    let deleted = create_game(&mut client).await?;
+
+    client
+        .delete_game(rpc::DeleteGameRequest {
+            game_id: deleted.id.clone(),
+            ..Default::default()
+        })
+        .await?;
+
+    let result = client
+        .get_game(rpc::GetGameRequest {
+            game_id: deleted.id.clone(),
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::NotFound));
+
+    let games = client
+        .list_games(rpc::ListGamesRequest::default())
+        .await?
+        .into_inner()
+        .games;
+
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].id, kept.id);
+
+    let result = client
+        .delete_game(rpc::DeleteGameRequest {
+            game_id: deleted.id,
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::NotFound));
+
+    Ok(())
+}
+
+pub async fn it_lists_deleted_games_on_request(mut client: Client) -> anyhow::Result<()> {
+    let deleted = create_game(&mut client).await?;
+
+    client
+        .delete_game(rpc::DeleteGameRequest {
+            game_id: deleted.id.clone(),
+            ..Default::default()
+        })
+        .await?;
+
+    let games = client
+        .list_games(rpc::ListGamesRequest {
+            include_deleted: true,
+        })
+        .await?
+        .into_inner()
+        .games;
+
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].id, deleted.id);
+
+    let summaries = client
+        .list_game_summaries(rpc::ListGameSummariesRequest {
+            include_deleted: true,
+        })
+        .await?
+        .into_inner()
+        .game_summaries;
+
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].id, deleted.id);
+
+    Ok(())
+}
+
+pub async fn it_deletes_game_once_per_request_id(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+    let request = rpc::DeleteGameRequest {
+        game_id: game.id.clone(),
+        request_id: "delete".to_string(),
+    };
+
+    client.delete_game(request.clone()).await?;
+    client.delete_game(request).await?;
+
+    let result = client
+        .get_game(rpc::GetGameRequest { game_id: game.id })
+        .await;
+
+    assert_eq!(code(result), Some(Code::NotFound));
+
+    Ok(())
+}
+
+pub async fn it_updates_game_settings(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let game = client
+        .update_game_settings(rpc::UpdateGameSettingsRequest {
+            game_id: game.id,
+            settings: Some(rpc::GameSettings {
+                players_number: 3,
+                points_limit: 501,
+                ..Default::default()
+            }),
+            update_mask: Some(prost_types::FieldMask {
+                paths: vec!["players_number".to_string(), "points_limit".to_string()],
+            }),
+            ..Default::default()
+        })
+        .await?
+        .into_inner();
+
+    assert_eq!(game.players_number, 3);
+    assert_eq!(game.points_limit, 501);
+    assert_eq!(game.player_points_to_win, 501);
+    assert_eq!(game.player_details.len(), 3);
+
+    count_points(&mut client, &game.id, &[60]).await?;
+
+    let result = client
+        .update_game_settings(rpc::UpdateGameSettingsRequest {
+            game_id: game.id,
+            settings: Some(rpc::GameSettings {
+                players_number: 2,
+                ..Default::default()
+            }),
+            update_mask: Some(prost_types::FieldMask {
+                paths: vec!["players_number".to_string()],
+            }),
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::FailedPrecondition));
+
+    Ok(())
+}
+
+pub async fn it_does_not_update_players_number_over_limit(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let result = client
+        .update_game_settings(rpc::UpdateGameSettingsRequest {
+            game_id: game.id,
+            settings: Some(rpc::GameSettings {
+                players_number: i32::MAX,
+                ..Default::default()
+            }),
+            update_mask: Some(prost_types::FieldMask {
+                paths: vec!["players_number".to_string()],
+            }),
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::InvalidArgument));
+
+    Ok(())
+}
+
+pub async fn it_does_not_update_unknown_game_setting(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let result = client
+        .update_game_settings(rpc::UpdateGameSettingsRequest {
+            game_id: game.id,
+            settings: Some(rpc::GameSettings::default()),
+            update_mask: Some(prost_types::FieldMask {
+                paths: vec!["rules".to_string()],
+            }),
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::InvalidArgument));
+
+    Ok(())
+}
+
+pub async fn it_scores_late_visit_zero(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let game = client
+        .update_game_settings(shot_clock_request(
+            &game.id,
+            prost_types::Duration {
+                seconds: 0,
+                nanos: 1_000_000,
+            },
+        ))
+        .await?
+        .into_inner();
+
+    assert!(game.shot_clock.is_some());
+    assert!(game.turn_start_time.is_none());
+
+    let game = count_points(&mut client, &game.id, &[60]).await?;
+
+    assert!(game.turn_start_time.is_some());
+
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    let game = count_points(&mut client, &game.id, &[45]).await?;
+    let points = &game.rounds[0].points;
+
+    assert!(!points[0].late);
+    assert!(points[1].late);
+    assert_eq!(points[1].value, 0);
+    assert_eq!(game.player_details[1].points_to_win, 301);
+    assert!(game.remaining_turn_time.is_some());
+
+    Ok(())
+}
+
+pub async fn it_does_not_time_submitted_visits(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    client
+        .update_game_settings(shot_clock_request(
+            &game.id,
+            prost_types::Duration {
+                seconds: 0,
+                nanos: 1_000_000,
+            },
+        ))
+        .await?;
+    count_points(&mut client, &game.id, &[60]).await?;
+
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    let game = client
+        .submit_visits(submit_visits_request(&game.id, &[45, 20]))
+        .await?
+        .into_inner()
+        .game
+        .unwrap_or_default();
+    let points: Vec<&rpc::Point> = game.rounds.iter().flat_map(|round| &round.points).collect();
+
+    assert!(points.iter().all(|point| !point.late));
+    assert_eq!(game.player_details[0].points_to_win, 221);
+    assert_eq!(game.player_details[1].points_to_win, 256);
+
+    Ok(())
+}
+
+pub async fn it_does_not_set_shot_clock_over_an_hour(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let result = client
+        .update_game_settings(shot_clock_request(
+            &game.id,
+            prost_types::Duration {
+                seconds: 3601,
+                nanos: 0,
+            },
+        ))
+        .await;
+
+    assert_eq!(code(result), Some(Code::InvalidArgument));
+
+    let result = client
+        .update_game_settings(shot_clock_request(
+            &game.id,
+            prost_types::Duration {
+                seconds: i64::MAX,
+                nanos: 0,
+            },
+        ))
+        .await;
+
+    assert_eq!(code(result), Some(Code::InvalidArgument));
+
+    Ok(())
+}
+
+pub async fn it_corrects_score(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+    count_points(&mut client, &game.id, &[60, 45, 100]).await?;
+
+    let game = client
+        .correct_score(correct_score_request(&game.id, 1, 1, 20))
+        .await?
+        .into_inner()
+        .game
+        .unwrap_or_default();
+
+    assert_eq!(game.player, "Player2");
+    assert_eq!(game.player_details[0].points_to_win, 181);
+    assert_eq!(game.player_details[1].points_to_win, 256);
+
+    let corrections = client
+        .list_score_corrections(rpc::ListScoreCorrectionsRequest { game_id: game.id })
+        .await?
+        .into_inner()
+        .score_corrections;
+
+    assert_eq!(corrections.len(), 1);
+    assert_eq!(corrections[0].round_number, 1);
+    assert_eq!(corrections[0].player_number, 1);
+    assert_eq!(corrections[0].previous_points, 60);
+    assert_eq!(corrections[0].points, 20);
+
+    Ok(())
+}
+
+pub async fn it_does_not_correct_score_finishing_game_early(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+    count_points(&mut client, &game.id, &[180, 0, 100, 0]).await?;
+
+    let result = client
+        .correct_score(correct_score_request(&game.id, 2, 1, 121))
+        .await;
+
+    assert_eq!(code(result), Some(Code::FailedPrecondition));
+
+    let result = client
+        .correct_score(correct_score_request(&game.id, 3, 1, 20))
+        .await;
+
+    assert_eq!(code(result), Some(Code::NotFound));
+
+    Ok(())
+}
+
+pub async fn it_gets_game_timeline(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+    count_points(&mut client, &game.id, &[60, 45, 100]).await?;
+
+    let steps = client
+        .get_game_timeline(rpc::GetGameTimelineRequest { game_id: game.id })
+        .await?
+        .into_inner()
+        .steps;
+
+    assert_eq!(steps.len(), 3);
+    assert_eq!((steps[0].round_number, steps[0].player_number), (1, 1));
+    assert_eq!(steps[0].point.clone().unwrap_or_default().value, 60);
+    assert_eq!(steps[0].player, "Player2");
+    assert_eq!(steps[0].player_details[0].points_to_win, 241);
+    assert_eq!(steps[0].player_details[1].points_to_win, 301);
+    assert!(steps[0].time.is_some());
+    assert_eq!((steps[2].round_number, steps[2].player_number), (2, 1));
+    assert_eq!(steps[2].state(), rpc::GameState::InProgress);
+    assert_eq!(steps[2].player_details[0].points_to_win, 141);
+    assert_eq!(steps[2].player_details[1].points_to_win, 256);
+
+    Ok(())
+}
+
+pub async fn it_gets_game_pace(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    assert!(game.average_visit_duration.is_none());
+    assert!(game.duration.is_none());
+
+    count_points(&mut client, &game.id, &[60, 45, 100]).await?;
+
+    let game = client
+        .get_game(rpc::GetGameRequest { game_id: game.id })
+        .await?
+        .into_inner();
+
+    assert!(game
+        .rounds
+        .iter()
+        .flat_map(|round| &round.points)
+        .all(|point| point.time.is_some()));
+    assert!(game.average_visit_duration.is_some());
+    assert!(game.duration.is_some());
+
+    Ok(())
+}
+
+pub async fn it_cancels_last_score(mut client: Client) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+    count_points(&mut client, &game.id, &[60, 45]).await?;
+    let request = rpc::CancelLastScoreRequest {
+        game_id: game.id.clone(),
+        request_id: "cancel".to_string(),
+    };
+
+    let cancelled = client
+        .cancel_last_score(request.clone())
+        .await?
+        .into_inner();
+    let replayed = client.cancel_last_score(request).await?.into_inner();
+
+    assert_eq!(replayed, cancelled);
+
+    let game = cancelled.game.unwrap_or_default();
+
+    assert_eq!(game.player, "Player2");
+    assert_eq!(game.player_details[0].points_to_win, 241);
+    assert_eq!(game.player_details[1].points_to_win, 301);
+
+    Ok(())
+}
+
+pub async fn it_does_not_cancel_score_of_not_started_game(
+    mut client: Client,
+) -> anyhow::Result<()> {
+    let game = create_game(&mut client).await?;
+
+    let result = client
+        .cancel_last_score(rpc::CancelLastScoreRequest {
+            game_id: game.id,
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::FailedPrecondition));
+
+    Ok(())
+}
+
+pub async fn it_does_not_get_unknown_game(mut client: Client) -> anyhow::Result<()> {
+    let result = client
+        .get_game(rpc::GetGameRequest {
+            game_id: Uuid::nil().to_string(),
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::NotFound));
+
+    Ok(())
+}
+
+pub async fn it_does_not_get_game_with_invalid_id(mut client: Client) -> anyhow::Result<()> {
+    let result = client
+        .get_game(rpc::GetGameRequest {
+            game_id: "abc".to_string(),
+        })
+        .await;
+
+    assert_eq!(code(result), Some(Code::InvalidArgument));
+
+    Ok(())
+}
+
+async fn create_game(client: &mut Client) -> anyhow::Result<rpc::Game> {
+    let game = client
+        .create_game(rpc::CreateGameRequest::default())
+        .await?
+        .into_inner();
+
+    Ok(game)
+}
+
+/// Counts every visit in turn, returning the game after the last one.
+async fn count_points(
+    client: &mut Client,
+    game_id: &str,
+    visits: &[i32],
+) -> Result<rpc::Game, tonic::Status> {
+    let mut game = None;
+
+    for &points in visits {
+        game = client
+            .count_points(rpc::CountPointsRequest {
+                game_id: game_id.to_string(),
+                points,
+                ..Default::default()
+            })
+            .await?
+            .into_inner()
+            .game;
+    }
+
+    Ok(game.unwrap_or_default())
+}
+
+fn correct_score_request(
+    game_id: &str,
+    round_number: i32,
+    player_number: i32,
+    points: i32,
+) -> rpc::CorrectScoreRequest {
+    rpc::CorrectScoreRequest {
+        game_id: game_id.to_string(),
+        round_number,
+        player_number,
+        points,
+        ..Default::default()
+    }
+}
+
+fn shot_clock_request(
+    game_id: &str,
+    turn_time_limit: prost_types::Duration,
+) -> rpc::UpdateGameSettingsRequest {
+    rpc::UpdateGameSettingsRequest {
+        game_id: game_id.to_string(),
+        settings: Some(rpc::GameSettings {
+            shot_clock: Some(rpc::ShotClock {
+                turn_time_limit: Some(turn_time_limit),
+                late_visit_policy: rpc::LateVisitPolicy::ScoreZero.into(),
+            }),
+            ..Default::default()
+        }),
+        update_mask: Some(prost_types::FieldMask {
+            paths: vec!["shot_clock".to_string()],
+        }),
+        ..Default::default()
+    }
+}
+
+fn submit_visits_request(game_id: &str, visits: &[i32]) -> rpc::SubmitVisitsRequest {
+    rpc::SubmitVisitsRequest {
+        game_id: game_id.to_string(),
+        visits: visits.iter().map(|&points| rpc::Visit { points }).collect(),
+        ..Default::default()
+    }
+}
+
+fn code<T>(result: Result<T, tonic::Status>) -> Option<Code> {
+    result.err().map(|status| status.code())
+}
@@ -1,18 +1,67 @@
-mod games_service;
+use api::{
+    game_updates::GameUpdates,
+    grpc_web,
+    metrics::Metrics,
+    playground::{rpc::games_server::GamesServer, Server},
+    shutdown::Shutdown,
+    store::{MemoryStore, Store},
+};
+use clap::Parser;
+use dataspine::{GameArchive, Role};
+use server_config::{ServerArgs, ServerConfig};
+use std::{fs::File, path::PathBuf, sync::Arc};
+use tonic::{service::interceptor::InterceptedService, Request, Status};
+use tonic_web::GrpcWebLayer;
+
+#[derive(Parser)]
+struct Args {
+    /// Games to start with, in the JSON format of `cli export`
+    #[arg(long, env = "DARTS_MOCK_FIXTURES")]
+    fixtures: Option<PathBuf>,
+
+    #[command(flatten)]
+    server: ServerArgs,
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let server_socket_address = "[::]:50051".parse().unwrap();
-    println!("Running api mock using http://{server_socket_address}");
+    let Args { fixtures, server } = Args::parse();
+    let config = ServerConfig::load(server)?;
+    config.init_logging();
+
+    let shutdown = Shutdown::listen();
+    let game_updates = GameUpdates::default();
+    let games = MemoryStore::new(game_updates.clone());
 
-    let games_service = games_service::GamesService::new();
+    if let Some(path) = fixtures {
+        let seeded = GameArchive::read_json(File::open(&path)?)?.into_games()?;
+        tracing::info!("Seeded {} games from {}", seeded.len(), path.display());
+        games.seed(seeded);
+    }
 
-    tonic::transport::Server::builder()
-        .add_service(games_service::rpc::games_server::GamesServer::new(
-            games_service,
+    let store = Store::Memory(games);
+    let metrics = Arc::new(Metrics::new(store.clone())?);
+    let server = Arc::new(Server::new(store, game_updates, metrics, shutdown.clone()));
+    tracing::info!("Running api mock using {}", config.grpc_url());
+
+    config
+        .grpc_server()?
+        .accept_http1(true)
+        .layer(grpc_web::cors_layer(&config.cors_allowed_origins)?)
+        .layer(GrpcWebLayer::new())
+        .add_service(InterceptedService::new(
+            GamesServer::from_arc(server),
+            authenticate,
         ))
-        .serve(server_socket_address)
+        .serve_with_shutdown(config.grpc_address, shutdown.requested())
         .await?;
 
     Ok(())
 }
+
+/// The mock does not check tokens, every caller may do everything.
+fn authenticate(mut request: Request<()>) -> Result<Request<()>, Status> {
+    request.extensions_mut().insert(Role::Admin);
+
+    Ok(request)
+}
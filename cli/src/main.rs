@@ -1,28 +1,152 @@
-use clap::Parser;
-use dataspine::Repo;
+use clap::{Parser, Subcommand, ValueEnum};
+use dataspine::{GameArchive, IssueApiTokenParameters, Repo, Role, VerifyGamesParameters};
 use playground::{
     coordinator::{self, CountScoreParameters, InitializeGameParameters},
-    InProgressState, NotStartedState, Number, Points, Score, State,
+    ExpectedTurn, InProgressState, NotStartedState, Number, Points, Score, State,
 };
-use std::io;
+use std::{fs::File, io, path::PathBuf};
+use uuid::Uuid;
 
 #[derive(Parser)]
 struct Args {
     #[arg(long)]
     database_url: String,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write all games to a file or standard output
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+
+        /// Standard output is used when omitted
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Insert games from a JSON export, skipping games that already exist
+    Import {
+        #[arg(long)]
+        input: PathBuf,
+    },
+
+    /// Play a single player game (default)
+    Play,
+
+    /// Manage bearer tokens of the api server
+    Token {
+        #[command(subcommand)]
+        command: TokenCommand,
+    },
+
+    /// Replay stored games and report rows inconsistent with their rounds
+    Verify {
+        /// Mark broken games so that listings skip them
+        #[arg(long)]
+        quarantine: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TokenCommand {
+    /// Create a token and print its secret, which can not be shown again
+    Issue {
+        /// Who or what the token is for
+        #[arg(long)]
+        name: String,
+
+        #[arg(long, value_enum)]
+        role: TokenRole,
+    },
+
+    /// Show all tokens, including revoked ones
+    List,
+
+    /// Reject the token from now on
+    Revoke { id: Uuid },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let Args { database_url } = Args::parse();
+    let Args {
+        database_url,
+        command,
+    } = Args::parse();
     let repo = Repo::from_database_url(&database_url).await?;
 
+    match command.unwrap_or(Command::Play) {
+        Command::Export { format, output } => export(&repo, format, output).await,
+        Command::Import { input } => import(&repo, input).await,
+        Command::Play => play(&repo).await,
+        Command::Token { command } => token(&repo, command).await,
+        Command::Verify { quarantine } => verify(&repo, quarantine).await,
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum ExportFormat {
+    /// Games with rounds, suitable for import
+    Json,
+
+    /// One line per visit, for spreadsheets
+    Csv,
+}
+
+#[derive(Clone, ValueEnum)]
+enum TokenRole {
+    /// Read games
+    Viewer,
+
+    /// Create games and count points
+    Scorer,
+
+    /// Everything
+    Admin,
+}
+
+async fn export(repo: &Repo, format: ExportFormat, output: Option<PathBuf>) -> anyhow::Result<()> {
+    let archive = repo.export_games().await?;
+
+    let writer: Box<dyn io::Write> = match output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    let writer = io::BufWriter::new(writer);
+
+    match format {
+        ExportFormat::Json => archive.write_json(writer)?,
+        ExportFormat::Csv => archive.write_csv(writer)?,
+    };
+
+    Ok(())
+}
+
+async fn import(repo: &Repo, input: PathBuf) -> anyhow::Result<()> {
+    let archive = GameArchive::read_json(File::open(input)?)?;
+    let report = repo.import_games(archive).await?;
+
+    println!(
+        "Imported {} games, skipped {} existing",
+        report.imported, report.skipped
+    );
+
+    Ok(())
+}
+
+async fn play(repo: &Repo) -> anyhow::Result<()> {
     let mut game = coordinator::initialize_game(InitializeGameParameters {
-        games: &repo,
+        games: repo,
         players_number: Number::new(1)?,
         points_limit: Points::new(101),
+        request_id: None,
+        shot_clock: None,
     })
-    .await?;
+    .await?
+    .into_game();
 
     loop {
         let prompt = match game.state() {
@@ -32,6 +156,10 @@ async fn main() -> anyhow::Result<()> {
                 println!("\nPlayer{} won", state.winner());
                 return Ok(());
             }
+            State::Abandoned(state) => {
+                println!("\nGame abandoned: {}", state.reason());
+                return Ok(());
+            }
         };
 
         display_prompt(&prompt);
@@ -45,13 +173,15 @@ async fn main() -> anyhow::Result<()> {
         };
 
         game = match coordinator::count_score(CountScoreParameters {
+            expected_turn: ExpectedTurn::default(),
             game_id: game.id().unwrap(),
-            games: &repo,
+            games: repo,
+            request_id: None,
             score,
         })
         .await
         {
-            Ok(game) => game,
+            Ok(requested) => requested.into_game(),
             Err(err) => {
                 println!("{}", err);
                 continue;
@@ -60,6 +190,62 @@ async fn main() -> anyhow::Result<()> {
     }
 }
 
+async fn verify(repo: &Repo, quarantine: bool) -> anyhow::Result<()> {
+    let report = repo
+        .verify_games(VerifyGamesParameters { quarantine })
+        .await?;
+
+    for issue in &report.issues {
+        println!("{} {}", issue.game_id, issue.description);
+    }
+
+    println!(
+        "\nVerified {} games, {} broken{}",
+        report.games_number,
+        report.issues.len(),
+        if quarantine && !report.issues.is_empty() {
+            ", quarantined"
+        } else {
+            ""
+        }
+    );
+
+    Ok(())
+}
+
+async fn token(repo: &Repo, command: TokenCommand) -> anyhow::Result<()> {
+    match command {
+        TokenCommand::Issue { name, role } => {
+            let issued = repo
+                .issue_api_token(IssueApiTokenParameters {
+                    name: &name,
+                    role: role.into(),
+                })
+                .await?;
+
+            println!("{} {}", issued.api_token.id, issued.secret);
+        }
+        TokenCommand::List => {
+            for api_token in repo.list_api_tokens().await? {
+                println!(
+                    "{} {} {} {}",
+                    api_token.id,
+                    api_token.role,
+                    api_token.name,
+                    if api_token.revoke_time.is_some() {
+                        "revoked"
+                    } else {
+                        "active"
+                    }
+                );
+            }
+        }
+        TokenCommand::Revoke { id } => repo.revoke_api_token(id).await?,
+    };
+
+    Ok(())
+}
+
 struct Prompt {
     player_number: Number,
     points_to_win: Points,
@@ -83,6 +269,16 @@ impl From<&InProgressState> for Prompt {
     }
 }
 
+impl From<TokenRole> for Role {
+    fn from(value: TokenRole) -> Self {
+        match value {
+            TokenRole::Viewer => Role::Viewer,
+            TokenRole::Scorer => Role::Scorer,
+            TokenRole::Admin => Role::Admin,
+        }
+    }
+}
+
 fn display_prompt(prompt: &Prompt) {
     let Prompt {
         player_number,
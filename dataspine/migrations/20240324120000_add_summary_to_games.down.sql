@@ -0,0 +1,5 @@
+ALTER TABLE playground.games
+    DROP COLUMN current_player_number,
+    DROP COLUMN current_points_to_win,
+    DROP COLUMN current_round_number,
+    DROP COLUMN winner;
@@ -0,0 +1,69 @@
+ALTER TABLE playground.games
+    ADD COLUMN current_player_number int,
+    ADD COLUMN current_points_to_win int,
+    ADD COLUMN current_round_number int,
+    ADD COLUMN winner int;
+
+UPDATE playground.games
+SET
+    current_player_number = 1,
+    current_points_to_win = points_limit,
+    current_round_number = 1
+WHERE rounds = '[]'::jsonb;
+
+WITH items AS (
+    SELECT
+        games.id,
+        (item ->> 'round_number')::int AS round_number,
+        (item ->> 'player_number')::int AS player_number,
+        CASE
+            WHEN item ->> 'points_kind' = 'regular' THEN (item ->> 'points')::int
+            ELSE 0
+        END AS points
+    FROM playground.games games
+    CROSS JOIN jsonb_array_elements(games.rounds) item
+), last_items AS (
+    SELECT DISTINCT ON (id) id, round_number, player_number
+    FROM items
+    ORDER BY id, round_number DESC, player_number DESC
+), totals AS (
+    SELECT id, player_number, SUM(points) AS points
+    FROM items
+    GROUP BY id, player_number
+), turns AS (
+    SELECT
+        games.id,
+        last_items.player_number AS last_player_number,
+        last_totals.points AS last_points,
+        CASE
+            WHEN last_items.player_number = games.players_number THEN 1
+            ELSE last_items.player_number + 1
+        END AS next_player_number,
+        CASE
+            WHEN last_items.player_number = games.players_number THEN last_items.round_number + 1
+            ELSE last_items.round_number
+        END AS next_round_number
+    FROM playground.games games
+    JOIN last_items ON last_items.id = games.id
+    JOIN totals last_totals
+        ON last_totals.id = games.id AND last_totals.player_number = last_items.player_number
+)
+UPDATE playground.games games
+SET
+    winner = CASE
+        WHEN turns.last_points = games.points_limit THEN turns.last_player_number
+    END,
+    current_player_number = CASE
+        WHEN turns.last_points <> games.points_limit THEN turns.next_player_number
+    END,
+    current_round_number = CASE
+        WHEN turns.last_points <> games.points_limit THEN turns.next_round_number
+    END,
+    current_points_to_win = CASE
+        WHEN turns.last_points <> games.points_limit
+        THEN games.points_limit - COALESCE(next_totals.points, 0)
+    END
+FROM turns
+LEFT JOIN totals next_totals
+    ON next_totals.id = turns.id AND next_totals.player_number = turns.next_player_number
+WHERE turns.id = games.id;
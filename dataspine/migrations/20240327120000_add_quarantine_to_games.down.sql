@@ -0,0 +1,3 @@
+ALTER TABLE playground.games
+    DROP COLUMN quarantine_reason,
+    DROP COLUMN quarantine_time;
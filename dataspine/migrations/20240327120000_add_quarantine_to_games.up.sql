@@ -0,0 +1,3 @@
+ALTER TABLE playground.games
+    ADD COLUMN quarantine_reason text,
+    ADD COLUMN quarantine_time timestamp(6) WITH time ZONE;
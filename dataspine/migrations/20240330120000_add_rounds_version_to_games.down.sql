@@ -0,0 +1 @@
+ALTER TABLE playground.games DROP COLUMN rounds_version;
@@ -0,0 +1,2 @@
+ALTER TABLE playground.games ADD COLUMN rounds_version int NOT NULL DEFAULT 1;
+ALTER TABLE playground.games ALTER COLUMN rounds_version DROP DEFAULT;
@@ -0,0 +1,2 @@
+DROP TRIGGER notify_game_update ON playground.games;
+DROP FUNCTION playground.notify_game_update();
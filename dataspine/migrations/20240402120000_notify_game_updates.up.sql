@@ -0,0 +1,11 @@
+CREATE FUNCTION playground.notify_game_update() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('playground_game_updates', NEW.id::text);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER notify_game_update
+    AFTER INSERT OR UPDATE ON playground.games
+    FOR EACH ROW
+    EXECUTE FUNCTION playground.notify_game_update();
@@ -0,0 +1 @@
+DROP TABLE playground.api_tokens;
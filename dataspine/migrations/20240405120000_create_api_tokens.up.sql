@@ -0,0 +1,8 @@
+CREATE TABLE playground.api_tokens (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    insert_time timestamp(6) WITH time ZONE NOT NULL DEFAULT now(),
+    name text NOT NULL,
+    revoke_time timestamp(6) WITH time ZONE,
+    role text NOT NULL CHECK (role IN ('admin', 'scorer', 'viewer')),
+    secret_hash text NOT NULL UNIQUE
+);
@@ -0,0 +1 @@
+DROP TABLE playground.game_requests;
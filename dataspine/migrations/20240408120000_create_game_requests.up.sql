@@ -0,0 +1,9 @@
+CREATE TABLE playground.game_requests (
+    game_id uuid NOT NULL REFERENCES playground.games (id) ON DELETE CASCADE,
+    insert_time timestamp(6) WITH time ZONE NOT NULL DEFAULT now(),
+    request_id text PRIMARY KEY,
+    rounds_number integer NOT NULL,
+    update_time timestamp(6) WITH time ZONE NOT NULL
+);
+
+CREATE INDEX game_requests_game_id_idx ON playground.game_requests (game_id);
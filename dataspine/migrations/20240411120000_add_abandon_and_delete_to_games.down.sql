@@ -0,0 +1,4 @@
+ALTER TABLE playground.games
+    DROP COLUMN abandon_reason,
+    DROP COLUMN abandon_time,
+    DROP COLUMN delete_time;
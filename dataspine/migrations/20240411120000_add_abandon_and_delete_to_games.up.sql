@@ -0,0 +1,4 @@
+ALTER TABLE playground.games
+    ADD COLUMN abandon_reason text,
+    ADD COLUMN abandon_time timestamp(6) WITH time ZONE,
+    ADD COLUMN delete_time timestamp(6) WITH time ZONE;
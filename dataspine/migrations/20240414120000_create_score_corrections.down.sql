@@ -0,0 +1 @@
+DROP TABLE playground.score_corrections;
@@ -0,0 +1,11 @@
+CREATE TABLE playground.score_corrections (
+    correct_time timestamp(6) WITH time ZONE NOT NULL,
+    game_id uuid NOT NULL REFERENCES playground.games (id) ON DELETE CASCADE,
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    player_number int NOT NULL,
+    points int NOT NULL,
+    previous_points int NOT NULL,
+    round_number int NOT NULL
+);
+
+CREATE INDEX score_corrections_game_id_idx ON playground.score_corrections (game_id);
@@ -0,0 +1,4 @@
+ALTER TABLE playground.games
+    DROP CONSTRAINT games_shot_clock_check,
+    DROP COLUMN late_visit_policy,
+    DROP COLUMN turn_time_limit_ms;
@@ -0,0 +1,5 @@
+ALTER TABLE playground.games
+    ADD COLUMN late_visit_policy text CHECK (late_visit_policy IN ('flag', 'score_zero')),
+    ADD COLUMN turn_time_limit_ms integer CHECK (turn_time_limit_ms > 0),
+    ADD CONSTRAINT games_shot_clock_check
+        CHECK ((late_visit_policy IS NULL) = (turn_time_limit_ms IS NULL));
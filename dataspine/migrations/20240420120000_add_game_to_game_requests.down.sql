@@ -0,0 +1,14 @@
+ALTER TABLE playground.game_requests
+    ADD COLUMN rounds_number integer,
+    ADD COLUMN update_time timestamp(6) WITH time ZONE;
+
+UPDATE playground.game_requests
+SET
+    rounds_number = jsonb_array_length(game -> 'rounds'),
+    update_time = (game ->> 'update_time')::timestamptz;
+
+ALTER TABLE playground.game_requests
+    ALTER COLUMN rounds_number SET NOT NULL,
+    ALTER COLUMN update_time SET NOT NULL,
+    DROP COLUMN game,
+    DROP COLUMN rounds_version;
@@ -0,0 +1,35 @@
+ALTER TABLE playground.game_requests
+    ADD COLUMN game jsonb,
+    ADD COLUMN rounds_version integer;
+
+-- Requests recorded so far only kept the number of rounds, their games are rebuilt
+-- from the rounds counted up to them.
+UPDATE playground.game_requests AS r
+SET
+    game = jsonb_build_object(
+        'abandon_reason', CASE WHEN g.abandon_time <= r.update_time THEN g.abandon_reason END,
+        'abandon_time', CASE WHEN g.abandon_time <= r.update_time THEN g.abandon_time END,
+        'create_time', g.insert_time,
+        'end_time', CASE WHEN jsonb_array_length(g.rounds) = r.rounds_number THEN g.end_time END,
+        'id', g.id,
+        'late_visit_policy', g.late_visit_policy,
+        'players_number', g.players_number,
+        'points_limit', g.points_limit,
+        'rounds', (
+            SELECT coalesce(jsonb_agg(item ORDER BY position), '[]'::jsonb)
+            FROM jsonb_array_elements(g.rounds) WITH ORDINALITY AS rounds (item, position)
+            WHERE position <= r.rounds_number
+        ),
+        'start_time', CASE WHEN r.rounds_number > 0 THEN g.start_time END,
+        'turn_time_limit_ms', g.turn_time_limit_ms,
+        'update_time', r.update_time
+    ),
+    rounds_version = g.rounds_version
+FROM playground.games AS g
+WHERE g.id = r.game_id;
+
+ALTER TABLE playground.game_requests
+    ALTER COLUMN game SET NOT NULL,
+    ALTER COLUMN rounds_version SET NOT NULL,
+    DROP COLUMN rounds_number,
+    DROP COLUMN update_time;
@@ -0,0 +1,3 @@
+UPDATE playground.games
+SET delete_time = now()
+WHERE id = $1 AND delete_time IS NULL
@@ -0,0 +1,7 @@
+SELECT
+    game,
+    game_id,
+    request_id,
+    rounds_version
+FROM playground.game_requests
+WHERE request_id = $1 AND ($2::uuid IS NULL OR game_id = $2)
@@ -0,0 +1,20 @@
+INSERT INTO playground.games (
+    abandon_reason,
+    abandon_time,
+    current_player_number,
+    current_points_to_win,
+    current_round_number,
+    end_time,
+    id,
+    insert_time,
+    late_visit_policy,
+    players_number,
+    points_limit,
+    rounds,
+    rounds_version,
+    start_time,
+    turn_time_limit_ms,
+    update_time,
+    winner
+) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+ON CONFLICT (id) DO NOTHING
@@ -0,0 +1,2 @@
+INSERT INTO playground.api_tokens (name, role, secret_hash) VALUES ($1, $2, $3)
+RETURNING id, insert_time, name, revoke_time, role, secret_hash
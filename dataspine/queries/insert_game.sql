@@ -1,8 +1,17 @@
 INSERT INTO playground.games (
+    abandon_reason,
+    abandon_time,
+    current_player_number,
+    current_points_to_win,
+    current_round_number,
     end_time,
+    late_visit_policy,
     players_number,
     points_limit,
     rounds,
-    start_time
-) VALUES ($1, $2, $3, $4, $5)
+    rounds_version,
+    start_time,
+    turn_time_limit_ms,
+    winner
+) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
 RETURNING id, insert_time, update_time
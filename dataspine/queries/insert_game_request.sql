@@ -0,0 +1,3 @@
+INSERT INTO playground.game_requests (game, game_id, request_id, rounds_version)
+VALUES ($1, $2, $3, $4)
+ON CONFLICT (request_id) DO NOTHING
@@ -0,0 +1,8 @@
+INSERT INTO playground.score_corrections (
+    correct_time,
+    game_id,
+    player_number,
+    points,
+    previous_points,
+    round_number
+) VALUES ($1, $2, $3, $4, $5, $6)
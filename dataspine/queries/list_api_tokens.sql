@@ -0,0 +1,9 @@
+SELECT
+    id,
+    insert_time,
+    name,
+    revoke_time,
+    role,
+    secret_hash
+FROM playground.api_tokens
+ORDER BY insert_time
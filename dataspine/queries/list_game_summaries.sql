@@ -0,0 +1,18 @@
+SELECT
+    abandon_reason,
+    abandon_time,
+    current_player_number,
+    current_points_to_win,
+    current_round_number,
+    end_time,
+    id,
+    insert_time,
+    players_number,
+    points_limit,
+    start_time,
+    update_time,
+    winner
+FROM playground.games
+WHERE ($1 OR delete_time IS NULL) AND quarantine_time IS NULL
+ORDER BY insert_time DESC
+LIMIT 10
@@ -1,12 +1,19 @@
 SELECT
+    abandon_reason,
+    abandon_time,
     end_time,
     id,
     insert_time,
+    late_visit_policy,
     players_number,
     points_limit,
-    rounds as "rounds!: Json<Vec<RoundsColumnItem>>",
+    rounds,
+    rounds_version,
     start_time,
-    update_time
+    turn_time_limit_ms,
+    update_time,
+    winner
 FROM playground.games
+WHERE ($1 OR delete_time IS NULL) AND quarantine_time IS NULL
 ORDER BY insert_time DESC
 LIMIT 10
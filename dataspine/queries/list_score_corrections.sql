@@ -0,0 +1,10 @@
+SELECT
+    correct_time,
+    game_id,
+    player_number,
+    points,
+    previous_points,
+    round_number
+FROM playground.score_corrections
+WHERE game_id = $1
+ORDER BY correct_time
@@ -0,0 +1,5 @@
+UPDATE playground.games
+SET
+    quarantine_reason = $2,
+    quarantine_time = now()
+WHERE id = $1
@@ -0,0 +1,3 @@
+UPDATE playground.api_tokens
+SET revoke_time = now()
+WHERE id = $1 AND revoke_time IS NULL
@@ -0,0 +1,18 @@
+SELECT
+    abandon_reason,
+    abandon_time,
+    end_time,
+    id,
+    insert_time,
+    late_visit_policy,
+    players_number,
+    points_limit,
+    rounds,
+    rounds_version,
+    start_time,
+    turn_time_limit_ms,
+    update_time,
+    winner
+FROM playground.games
+WHERE delete_time IS NULL AND quarantine_time IS NULL
+ORDER BY insert_time
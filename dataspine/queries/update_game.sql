@@ -1,10 +1,19 @@
 UPDATE playground.games
 SET
-    end_time = $2,
-    players_number = $3,
-    points_limit = $4,
-    rounds = $5,
-    start_time = $6,
+    abandon_reason = $2,
+    abandon_time = $3,
+    current_player_number = $4,
+    current_points_to_win = $5,
+    current_round_number = $6,
+    end_time = $7,
+    late_visit_policy = $8,
+    players_number = $9,
+    points_limit = $10,
+    rounds = $11,
+    rounds_version = $12,
+    start_time = $13,
+    turn_time_limit_ms = $14,
+    winner = $15,
     update_time = default
-WHERE id = $1
+WHERE id = $1 AND delete_time IS NULL AND update_time = $16
 RETURNING update_time;
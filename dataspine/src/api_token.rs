@@ -0,0 +1,128 @@
+use crate::{ApiTokenRow, InsertApiToken, ListApiTokens, Repo, RevokeApiToken};
+use chrono::{DateTime, Utc};
+use playground::{Error, Reason};
+use sha2::{Digest, Sha256};
+use std::{fmt::Display, str::FromStr};
+use uuid::Uuid;
+
+/// Access level of an API token. Each role is allowed everything the lower ones are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Viewer,
+    Scorer,
+    Admin,
+}
+
+pub struct ApiToken {
+    pub id: Uuid,
+    pub insert_time: DateTime<Utc>,
+    pub name: String,
+    pub revoke_time: Option<DateTime<Utc>>,
+    pub role: Role,
+    pub secret_hash: String,
+}
+
+pub struct IssuedApiToken {
+    pub api_token: ApiToken,
+
+    /// Only returned at issue time, the database keeps a hash of it.
+    pub secret: String,
+}
+
+pub struct IssueApiTokenParameters<'a> {
+    pub name: &'a str,
+    pub role: Role,
+}
+
+impl Repo {
+    pub async fn issue_api_token(
+        &self,
+        parameters: IssueApiTokenParameters<'_>,
+    ) -> Result<IssuedApiToken, Error> {
+        let IssueApiTokenParameters { name, role } = parameters;
+
+        let secret = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+
+        let api_token = self
+            .conn()
+            .await?
+            .insert_api_token(name, role.as_str(), &secret_hash(&secret))
+            .await?
+            .try_into()?;
+
+        Ok(IssuedApiToken { api_token, secret })
+    }
+
+    pub async fn list_api_tokens(&self) -> Result<Vec<ApiToken>, Error> {
+        self.conn()
+            .await?
+            .list_api_tokens()
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect()
+    }
+
+    pub async fn revoke_api_token(&self, id: Uuid) -> Result<(), Error> {
+        if !self.conn().await?.revoke_api_token(id).await? {
+            return Err(Error::NotFound(
+                Reason::ApiTokenNotFound,
+                format!("Active API token {id}"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Scorer => "scorer",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "viewer" => Ok(Role::Viewer),
+            "scorer" => Ok(Role::Scorer),
+            "admin" => Ok(Role::Admin),
+            _ => Err(Error::InvalidArgument(
+                Reason::UnknownRole,
+                format!("Unknown role: {s}"),
+            )),
+        }
+    }
+}
+
+impl TryFrom<ApiTokenRow> for ApiToken {
+    type Error = Error;
+
+    fn try_from(row: ApiTokenRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            insert_time: row.insert_time,
+            name: row.name,
+            revoke_time: row.revoke_time,
+            role: row.role.parse()?,
+            secret_hash: row.secret_hash,
+        })
+    }
+}
+
+/// Hash under which the secret of a token is stored.
+pub fn secret_hash(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use playground::Error;
+use uuid::Uuid;
+
+pub trait InsertApiToken {
+    async fn insert_api_token(
+        &mut self,
+        name: &str,
+        role: &str,
+        secret_hash: &str,
+    ) -> Result<ApiTokenRow, Error>;
+}
+
+pub trait ListApiTokens {
+    async fn list_api_tokens(&mut self) -> Result<Vec<ApiTokenRow>, Error>;
+}
+
+pub trait RevokeApiToken {
+    /// Returns `false` when there is no active token with the id.
+    async fn revoke_api_token(&mut self, id: Uuid) -> Result<bool, Error>;
+}
+
+pub struct ApiTokenRow {
+    pub id: Uuid,
+    pub insert_time: DateTime<Utc>,
+    pub name: String,
+    pub revoke_time: Option<DateTime<Utc>>,
+    pub role: String,
+    pub secret_hash: String,
+}
@@ -0,0 +1,230 @@
+use crate::{
+    decode_rounds, GameRow, ImportGame, Repo, RoundsColumnItem, ScanGames, ShotClockColumns,
+    ROUNDS_COLUMN_VERSION,
+};
+use chrono::{DateTime, Utc};
+use playground::{Error, Game, Reason, State};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{Read, Write};
+use uuid::Uuid;
+
+/// Portable copy of stored games, used for backups and migrations between instances.
+#[derive(Deserialize, Serialize)]
+pub struct GameArchive {
+    pub games: Vec<ArchivedGame>,
+    pub rounds_version: i32,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ArchivedGame {
+    pub abandon_reason: Option<String>,
+    pub abandon_time: Option<DateTime<Utc>>,
+    pub create_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub id: Uuid,
+    /// Absent, like the turn time limit, in archives of games without a shot clock.
+    pub late_visit_policy: Option<String>,
+    pub players_number: i32,
+    pub points_limit: i32,
+    pub rounds: Value,
+    pub start_time: Option<DateTime<Utc>>,
+    pub turn_time_limit_ms: Option<i32>,
+    pub update_time: DateTime<Utc>,
+}
+
+pub struct ImportReport {
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+impl GameArchive {
+    /// Replays every archived game, failing on the first invalid one.
+    pub fn into_games(self) -> Result<Vec<Game>, Error> {
+        let GameArchive {
+            games,
+            rounds_version,
+        } = self;
+
+        games
+            .into_iter()
+            .map(|game| load_archived_game(game, rounds_version))
+            .collect()
+    }
+
+    pub fn read_json(reader: impl Read) -> Result<Self, Error> {
+        let archive = serde_json::from_reader(reader).map_err(eyre::Report::new)?;
+
+        Ok(archive)
+    }
+
+    /// Writes one line per visit, repeating game columns on every line.
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<(), Error> {
+        writeln!(
+            writer,
+            "game_id,game_create_time,players_number,points_limit,round_number,player_number,points_kind,points,time,late"
+        )
+        .map_err(eyre::Report::new)?;
+
+        for game in &self.games {
+            let rounds = decode_rounds(self.rounds_version, game.rounds.clone())?;
+
+            for round in rounds {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{},{},{},{},{},{}",
+                    game.id,
+                    game.create_time.to_rfc3339(),
+                    game.players_number,
+                    game.points_limit,
+                    round.round_number,
+                    round.player_number,
+                    round.points_kind.as_str(),
+                    round.points,
+                    round.time.map(|time| time.to_rfc3339()).unwrap_or_default(),
+                    round.late
+                )
+                .map_err(eyre::Report::new)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn write_json(&self, writer: impl Write) -> Result<(), Error> {
+        serde_json::to_writer_pretty(writer, self).map_err(eyre::Report::new)?;
+
+        Ok(())
+    }
+}
+
+impl Repo {
+    /// Exports all games that are not quarantined, with rounds in the current layout.
+    pub async fn export_games(&self) -> Result<GameArchive, Error> {
+        let games = self
+            .conn()
+            .await?
+            .scan_games()
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<ArchivedGame>, Error>>()?;
+
+        Ok(GameArchive {
+            games,
+            rounds_version: ROUNDS_COLUMN_VERSION,
+        })
+    }
+
+    /// Replays every archived game before inserting anything.
+    /// Games with already existing ids are skipped.
+    pub async fn import_games(&self, archive: GameArchive) -> Result<ImportReport, Error> {
+        let games = archive.into_games()?;
+        let mut transaction = self.transaction().await?;
+        let mut imported = 0;
+
+        for game in &games {
+            if transaction.import_game(game).await? {
+                imported += 1;
+            }
+        }
+
+        transaction.commit().await.map_err(eyre::Report::new)?;
+
+        Ok(ImportReport {
+            imported,
+            skipped: games.len() - imported,
+        })
+    }
+}
+
+impl ArchivedGame {
+    /// Row of the game, its rounds in the `rounds_version` layout.
+    pub(crate) fn into_row(self, rounds_version: i32) -> GameRow {
+        GameRow {
+            abandon_reason: self.abandon_reason,
+            abandon_time: self.abandon_time,
+            end_time: self.end_time,
+            id: self.id,
+            insert_time: self.create_time,
+            late_visit_policy: self.late_visit_policy,
+            players_number: self.players_number,
+            points_limit: self.points_limit,
+            rounds: self.rounds,
+            rounds_version,
+            start_time: self.start_time,
+            turn_time_limit_ms: self.turn_time_limit_ms,
+            update_time: self.update_time,
+            winner: None,
+        }
+    }
+}
+
+impl TryFrom<&Game> for ArchivedGame {
+    type Error = Error;
+
+    /// Rounds are in the current layout.
+    fn try_from(game: &Game) -> Result<Self, Self::Error> {
+        let (Some(id), Some(create_time), Some(update_time)) =
+            (game.id(), game.create_time(), game.update_time())
+        else {
+            return Err(eyre::eyre!("Trying to archive unsaved game").into());
+        };
+
+        let (abandon_reason, abandon_time) = match game.state() {
+            State::Abandoned(state) => (Some(state.reason().to_string()), Some(state.time())),
+            State::NotStarted(_) | State::InProgress(_) | State::Finished(_) => (None, None),
+        };
+        let rounds: Vec<RoundsColumnItem> = game.rounds().iter().map(Into::into).collect();
+        let ShotClockColumns {
+            late_visit_policy,
+            turn_time_limit_ms,
+        } = game.shot_clock().into();
+
+        Ok(Self {
+            abandon_reason,
+            abandon_time,
+            create_time,
+            end_time: game.end_time(),
+            id,
+            late_visit_policy,
+            players_number: game.players_number().value() as i32,
+            points_limit: game.points_limit().value().into(),
+            rounds: serde_json::to_value(rounds).map_err(eyre::Report::new)?,
+            start_time: game.start_time(),
+            turn_time_limit_ms,
+            update_time,
+        })
+    }
+}
+
+impl TryFrom<GameRow> for ArchivedGame {
+    type Error = Error;
+
+    fn try_from(row: GameRow) -> Result<Self, Self::Error> {
+        let rounds: Vec<RoundsColumnItem> = decode_rounds(row.rounds_version, row.rounds)?;
+
+        Ok(Self {
+            abandon_reason: row.abandon_reason,
+            abandon_time: row.abandon_time,
+            create_time: row.insert_time,
+            end_time: row.end_time,
+            id: row.id,
+            late_visit_policy: row.late_visit_policy,
+            players_number: row.players_number,
+            points_limit: row.points_limit,
+            rounds: serde_json::to_value(rounds).map_err(eyre::Report::new)?,
+            start_time: row.start_time,
+            turn_time_limit_ms: row.turn_time_limit_ms,
+            update_time: row.update_time,
+        })
+    }
+}
+
+fn load_archived_game(game: ArchivedGame, rounds_version: i32) -> Result<Game, Error> {
+    let id = game.id;
+
+    game.into_row(rounds_version)
+        .try_into()
+        .map_err(|err| Error::InvalidArgument(Reason::InvalidGame, format!("Game {id}: {err}")))
+}
@@ -0,0 +1,26 @@
+use playground::Error;
+use serde_json::Value;
+use uuid::Uuid;
+
+pub trait FindGameRequest {
+    /// Finds a request applied to any game when `game_id` is `None`.
+    async fn find_game_request(
+        &mut self,
+        request_id: &str,
+        game_id: Option<Uuid>,
+    ) -> Result<Option<GameRequestRow>, Error>;
+}
+
+pub trait InsertGameRequest {
+    /// Returns `false` when the request id is taken.
+    async fn insert_game_request(&mut self, row: &GameRequestRow) -> Result<bool, Error>;
+}
+
+/// Game changing request, with the game as the change left it.
+pub struct GameRequestRow {
+    /// [`ArchivedGame`](crate::ArchivedGame), its rounds in the `rounds_version` layout.
+    pub game: Value,
+    pub game_id: Uuid,
+    pub request_id: String,
+    pub rounds_version: i32,
+}
@@ -1,9 +1,18 @@
-use chrono::{DateTime, Utc};
-use playground::{Error, Game};
-use serde::{Deserialize, Serialize};
-use sqlx::types::Json;
+use chrono::{DateTime, Duration, Utc};
+use playground::{Error, Game, LateVisitPolicy, NewShotClockParameters, ShotClock};
+use serde_json::Value;
 use uuid::Uuid;
 
+pub trait DeleteGame {
+    /// Marks the game deleted. Returns `false` when there is no such game left.
+    async fn delete_game(&mut self, id: Uuid) -> Result<bool, Error>;
+}
+
+pub trait ImportGame {
+    /// Inserts the game keeping its id and times. Returns `false` when the id is taken.
+    async fn import_game(&mut self, game: &Game) -> Result<bool, Error>;
+}
+
 pub trait InsertGame {
     async fn insert_game(&mut self, game: &mut Game) -> Result<(), Error>;
 }
@@ -13,28 +22,85 @@ pub trait FindGame {
 }
 
 pub trait ListGames {
-    async fn list_games(&mut self) -> Result<Vec<GameRow>, Error>;
+    async fn list_games(&mut self, include_deleted: bool) -> Result<Vec<GameRow>, Error>;
+}
+
+pub trait QuarantineGame {
+    async fn quarantine_game(&mut self, id: Uuid, reason: &str) -> Result<(), Error>;
+}
+
+pub trait ScanGames {
+    async fn scan_games(&mut self) -> Result<Vec<GameRow>, Error>;
 }
 
 pub trait UpdateGame {
+    /// Fails with [`Error::Aborted`] when the game was saved since it was read.
     async fn update_game(&mut self, game: &mut Game) -> Result<(), Error>;
 }
 
 pub struct GameRow {
+    pub abandon_reason: Option<String>,
+    pub abandon_time: Option<DateTime<Utc>>,
     pub end_time: Option<DateTime<Utc>>,
     pub id: Uuid,
     pub insert_time: DateTime<Utc>,
+    pub late_visit_policy: Option<String>,
     pub players_number: i32,
     pub points_limit: i32,
-    pub rounds: Json<Vec<RoundsColumnItem>>,
+    pub rounds: Value,
+    pub rounds_version: i32,
     pub start_time: Option<DateTime<Utc>>,
+    pub turn_time_limit_ms: Option<i32>,
     pub update_time: DateTime<Utc>,
+    pub winner: Option<i32>,
 }
 
-#[derive(Deserialize, Serialize, Clone)]
-pub struct RoundsColumnItem {
-    pub round_number: i32,
-    pub player_number: i32,
-    pub points_kind: String,
-    pub points: i32,
+/// Shot clock of a game, both columns are set or neither is.
+pub struct ShotClockColumns {
+    pub late_visit_policy: Option<String>,
+    pub turn_time_limit_ms: Option<i32>,
+}
+
+impl ShotClockColumns {
+    pub fn shot_clock(self) -> Result<Option<ShotClock>, Error> {
+        let (Some(late_visit_policy), Some(turn_time_limit_ms)) =
+            (self.late_visit_policy, self.turn_time_limit_ms)
+        else {
+            return Ok(None);
+        };
+
+        let late_visit_policy = match late_visit_policy.as_str() {
+            "flag" => LateVisitPolicy::Flag,
+            "score_zero" => LateVisitPolicy::ScoreZero,
+            policy => return Err(eyre::eyre!("Unknown late visit policy: {policy}").into()),
+        };
+
+        let shot_clock = ShotClock::new(NewShotClockParameters {
+            late_visit_policy,
+            turn_time_limit: Duration::milliseconds(turn_time_limit_ms.into()),
+        })?;
+
+        Ok(Some(shot_clock))
+    }
+}
+
+impl From<Option<&ShotClock>> for ShotClockColumns {
+    fn from(value: Option<&ShotClock>) -> Self {
+        let Some(shot_clock) = value else {
+            return Self {
+                late_visit_policy: None,
+                turn_time_limit_ms: None,
+            };
+        };
+
+        let late_visit_policy = match shot_clock.late_visit_policy() {
+            LateVisitPolicy::Flag => "flag",
+            LateVisitPolicy::ScoreZero => "score_zero",
+        };
+
+        Self {
+            late_visit_policy: Some(late_visit_policy.to_string()),
+            turn_time_limit_ms: Some(shot_clock.turn_time_limit().num_milliseconds() as i32),
+        }
+    }
 }
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use playground::Error;
+use uuid::Uuid;
+
+pub trait ListGameSummaries {
+    async fn list_game_summaries(
+        &mut self,
+        include_deleted: bool,
+    ) -> Result<Vec<GameSummaryRow>, Error>;
+}
+
+pub struct GameSummaryRow {
+    pub abandon_reason: Option<String>,
+    pub abandon_time: Option<DateTime<Utc>>,
+    pub current_player_number: Option<i32>,
+    pub current_points_to_win: Option<i32>,
+    pub current_round_number: Option<i32>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub id: Uuid,
+    pub insert_time: DateTime<Utc>,
+    pub players_number: i32,
+    pub points_limit: i32,
+    pub start_time: Option<DateTime<Utc>>,
+    pub update_time: DateTime<Utc>,
+    pub winner: Option<i32>,
+}
@@ -0,0 +1,39 @@
+use crate::Repo;
+use playground::Error;
+use sqlx::postgres::PgListener;
+use uuid::Uuid;
+
+/// Channel notified by the `playground.games` trigger with the id of every inserted or updated game.
+const GAME_UPDATES_CHANNEL: &str = "playground_game_updates";
+
+/// Receives ids of changed games, including changes made by other processes.
+pub struct GameUpdates {
+    listener: PgListener,
+}
+
+impl GameUpdates {
+    /// Waits for the next game change.
+    /// Reconnects on connection loss, notifications sent meanwhile are lost.
+    pub async fn recv(&mut self) -> Result<Uuid, Error> {
+        let notification = self.listener.recv().await.map_err(eyre::Report::new)?;
+
+        let id = Uuid::parse_str(notification.payload()).map_err(eyre::Report::new)?;
+
+        Ok(id)
+    }
+}
+
+impl Repo {
+    pub async fn listen_game_updates(&self) -> Result<GameUpdates, Error> {
+        let mut listener = PgListener::connect_with(&self.pool)
+            .await
+            .map_err(eyre::Report::new)?;
+
+        listener
+            .listen(GAME_UPDATES_CHANNEL)
+            .await
+            .map_err(eyre::Report::new)?;
+
+        Ok(GameUpdates { listener })
+    }
+}
@@ -1,7 +1,32 @@
+mod api_token;
+mod api_token_row;
+mod archive;
+mod game_request_row;
 mod game_row;
+mod game_summary_row;
+mod game_updates;
 mod postgres;
 mod repo;
+mod rounds_column;
+mod score_correction_row;
+mod verification;
 
-pub use repo::Repo;
+pub use api_token::{secret_hash, ApiToken, IssueApiTokenParameters, IssuedApiToken, Role};
+pub use archive::{ArchivedGame, GameArchive, ImportReport};
+pub use game_updates::GameUpdates;
+pub use repo::{ConnectParameters, PoolStatus, Repo};
+pub use verification::{GameIssue, VerificationReport, VerifyGamesParameters};
 
-pub(crate) use game_row::{FindGame, GameRow, InsertGame, ListGames, RoundsColumnItem, UpdateGame};
+pub(crate) use api_token_row::{ApiTokenRow, InsertApiToken, ListApiTokens, RevokeApiToken};
+pub(crate) use game_request_row::{FindGameRequest, GameRequestRow, InsertGameRequest};
+pub(crate) use game_row::{
+    DeleteGame, FindGame, GameRow, ImportGame, InsertGame, ListGames, QuarantineGame, ScanGames,
+    ShotClockColumns, UpdateGame,
+};
+pub(crate) use game_summary_row::{GameSummaryRow, ListGameSummaries};
+pub(crate) use rounds_column::{
+    decode_rounds, PointsKind, RoundsColumnItem, ROUNDS_COLUMN_VERSION,
+};
+pub(crate) use score_correction_row::{
+    InsertScoreCorrection, ListScoreCorrections, ScoreCorrectionRow,
+};
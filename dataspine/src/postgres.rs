@@ -1,10 +1,29 @@
-use crate::{FindGame, GameRow, InsertGame, ListGames, RoundsColumnItem, UpdateGame};
+use crate::{
+    ApiTokenRow, DeleteGame, FindGame, FindGameRequest, GameRequestRow, GameRow, GameSummaryRow,
+    ImportGame, InsertApiToken, InsertGame, InsertGameRequest, InsertScoreCorrection,
+    ListApiTokens, ListGameSummaries, ListGames, ListScoreCorrections, QuarantineGame,
+    RevokeApiToken, RoundsColumnItem, ScanGames, ScoreCorrectionRow, ShotClockColumns, UpdateGame,
+    ROUNDS_COLUMN_VERSION,
+};
 use chrono::{DateTime, Utc};
-use playground::{Error, Game};
+use playground::{Error, Game, Reason, State};
 use sqlx::{types::Json, PgConnection};
 use uuid::Uuid;
 
+impl DeleteGame for PgConnection {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete_game(&mut self, id: Uuid) -> Result<bool, Error> {
+        let result = sqlx::query_file!("queries/delete_game.sql", id)
+            .execute(self)
+            .await
+            .map_err(eyre::Report::new)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
 impl FindGame for PgConnection {
+    #[tracing::instrument(level = "debug", skip(self))]
     async fn find_game(&mut self, id: Uuid) -> Result<Option<GameRow>, Error> {
         let row = sqlx::query_file_as!(GameRow, "queries/find_game.sql", id,)
             .fetch_optional(self)
@@ -15,7 +34,110 @@ impl FindGame for PgConnection {
     }
 }
 
+impl FindGameRequest for PgConnection {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn find_game_request(
+        &mut self,
+        request_id: &str,
+        game_id: Option<Uuid>,
+    ) -> Result<Option<GameRequestRow>, Error> {
+        let row = sqlx::query_file_as!(
+            GameRequestRow,
+            "queries/find_game_request.sql",
+            request_id,
+            game_id
+        )
+        .fetch_optional(self)
+        .await
+        .map_err(eyre::Report::new)?;
+
+        Ok(row)
+    }
+}
+
+impl ImportGame for PgConnection {
+    #[tracing::instrument(level = "debug", skip_all, fields(game_id = ?game.id()))]
+    async fn import_game(&mut self, game: &Game) -> Result<bool, Error> {
+        let id = game
+            .id()
+            .ok_or(eyre::eyre!("Trying to import game without id"))?;
+        let create_time = game
+            .create_time()
+            .ok_or(eyre::eyre!("Trying to import game without create time"))?;
+        let update_time = game
+            .update_time()
+            .ok_or(eyre::eyre!("Trying to import game without update time"))?;
+        let SummaryColumns {
+            abandon_reason,
+            abandon_time,
+            current_player_number,
+            current_points_to_win,
+            current_round_number,
+            winner,
+        } = game.state().into();
+        let end_time = game.end_time();
+        let players_number = game.players_number().value() as i32;
+        let points_limit = game.points_limit().value() as i32;
+        let rounds: Vec<RoundsColumnItem> = game.rounds().iter().map(Into::into).collect();
+        let ShotClockColumns {
+            late_visit_policy,
+            turn_time_limit_ms,
+        } = game.shot_clock().into();
+        let start_time = game.start_time();
+
+        let result = sqlx::query_file!(
+            "queries/import_game.sql",
+            abandon_reason,
+            abandon_time,
+            current_player_number,
+            current_points_to_win,
+            current_round_number,
+            end_time,
+            id,
+            create_time,
+            late_visit_policy,
+            players_number,
+            points_limit,
+            Json(rounds) as _,
+            ROUNDS_COLUMN_VERSION,
+            start_time,
+            turn_time_limit_ms,
+            update_time,
+            winner
+        )
+        .execute(self)
+        .await
+        .map_err(eyre::Report::new)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+impl InsertApiToken for PgConnection {
+    #[tracing::instrument(level = "debug", skip(self, secret_hash))]
+    async fn insert_api_token(
+        &mut self,
+        name: &str,
+        role: &str,
+        secret_hash: &str,
+    ) -> Result<ApiTokenRow, Error> {
+        let row = sqlx::query_file_as!(
+            ApiTokenRow,
+            "queries/insert_api_token.sql",
+            name,
+            role,
+            secret_hash
+        )
+        .fetch_one(self)
+        .await
+        .map_err(eyre::Report::new)?;
+
+        Ok(row)
+    }
+}
+
 impl InsertGame for PgConnection {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn insert_game(&mut self, game: &mut Game) -> Result<(), Error> {
         struct InsertReturnValues {
             id: Uuid,
@@ -23,20 +145,41 @@ impl InsertGame for PgConnection {
             update_time: DateTime<Utc>,
         }
 
+        let SummaryColumns {
+            abandon_reason,
+            abandon_time,
+            current_player_number,
+            current_points_to_win,
+            current_round_number,
+            winner,
+        } = game.state().into();
         let end_time = game.end_time();
         let players_number = game.players_number().value() as i32;
         let points_limit = game.points_limit().value() as i32;
         let rounds: Vec<RoundsColumnItem> = game.rounds().iter().map(Into::into).collect();
+        let ShotClockColumns {
+            late_visit_policy,
+            turn_time_limit_ms,
+        } = game.shot_clock().into();
         let start_time = game.start_time();
 
         let values = sqlx::query_file_as!(
             InsertReturnValues,
             "queries/insert_game.sql",
+            abandon_reason,
+            abandon_time,
+            current_player_number,
+            current_points_to_win,
+            current_round_number,
             end_time,
+            late_visit_policy,
             players_number,
             points_limit,
             Json(rounds) as _,
-            start_time
+            ROUNDS_COLUMN_VERSION,
+            start_time,
+            turn_time_limit_ms,
+            winner
         )
         .fetch_one(self)
         .await
@@ -50,9 +193,134 @@ impl InsertGame for PgConnection {
     }
 }
 
+impl InsertGameRequest for PgConnection {
+    #[tracing::instrument(level = "debug", skip_all, fields(game_id = %row.game_id, request_id = row.request_id))]
+    async fn insert_game_request(&mut self, row: &GameRequestRow) -> Result<bool, Error> {
+        let result = sqlx::query_file!(
+            "queries/insert_game_request.sql",
+            row.game,
+            row.game_id,
+            row.request_id,
+            row.rounds_version
+        )
+        .execute(self)
+        .await
+        .map_err(eyre::Report::new)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+impl InsertScoreCorrection for PgConnection {
+    #[tracing::instrument(level = "debug", skip_all, fields(game_id = %row.game_id))]
+    async fn insert_score_correction(&mut self, row: &ScoreCorrectionRow) -> Result<(), Error> {
+        sqlx::query_file!(
+            "queries/insert_score_correction.sql",
+            row.correct_time,
+            row.game_id,
+            row.player_number,
+            row.points,
+            row.previous_points,
+            row.round_number
+        )
+        .execute(self)
+        .await
+        .map_err(eyre::Report::new)?;
+
+        Ok(())
+    }
+}
+
+impl ListApiTokens for PgConnection {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn list_api_tokens(&mut self) -> Result<Vec<ApiTokenRow>, Error> {
+        let rows = sqlx::query_file_as!(ApiTokenRow, "queries/list_api_tokens.sql",)
+            .fetch_all(self)
+            .await
+            .map_err(eyre::Report::new)?;
+
+        Ok(rows)
+    }
+}
+
 impl ListGames for PgConnection {
-    async fn list_games(&mut self) -> Result<Vec<GameRow>, Error> {
-        let rows = sqlx::query_file_as!(GameRow, "queries/list_games.sql",)
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn list_games(&mut self, include_deleted: bool) -> Result<Vec<GameRow>, Error> {
+        let rows = sqlx::query_file_as!(GameRow, "queries/list_games.sql", include_deleted)
+            .fetch_all(self)
+            .await
+            .map_err(eyre::Report::new)?;
+
+        Ok(rows)
+    }
+}
+
+impl ListGameSummaries for PgConnection {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn list_game_summaries(
+        &mut self,
+        include_deleted: bool,
+    ) -> Result<Vec<GameSummaryRow>, Error> {
+        let rows = sqlx::query_file_as!(
+            GameSummaryRow,
+            "queries/list_game_summaries.sql",
+            include_deleted
+        )
+        .fetch_all(self)
+        .await
+        .map_err(eyre::Report::new)?;
+
+        Ok(rows)
+    }
+}
+
+impl ListScoreCorrections for PgConnection {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn list_score_corrections(
+        &mut self,
+        game_id: Uuid,
+    ) -> Result<Vec<ScoreCorrectionRow>, Error> {
+        let rows = sqlx::query_file_as!(
+            ScoreCorrectionRow,
+            "queries/list_score_corrections.sql",
+            game_id
+        )
+        .fetch_all(self)
+        .await
+        .map_err(eyre::Report::new)?;
+
+        Ok(rows)
+    }
+}
+
+impl QuarantineGame for PgConnection {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn quarantine_game(&mut self, id: Uuid, reason: &str) -> Result<(), Error> {
+        sqlx::query_file!("queries/quarantine_game.sql", id, reason)
+            .execute(self)
+            .await
+            .map_err(eyre::Report::new)?;
+
+        Ok(())
+    }
+}
+
+impl RevokeApiToken for PgConnection {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn revoke_api_token(&mut self, id: Uuid) -> Result<bool, Error> {
+        let result = sqlx::query_file!("queries/revoke_api_token.sql", id)
+            .execute(self)
+            .await
+            .map_err(eyre::Report::new)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+impl ScanGames for PgConnection {
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn scan_games(&mut self) -> Result<Vec<GameRow>, Error> {
+        let rows = sqlx::query_file_as!(GameRow, "queries/scan_games.sql",)
             .fetch_all(self)
             .await
             .map_err(eyre::Report::new)?;
@@ -62,31 +330,117 @@ impl ListGames for PgConnection {
 }
 
 impl UpdateGame for PgConnection {
+    #[tracing::instrument(level = "debug", skip_all, fields(game_id = ?game.id()))]
     async fn update_game(&mut self, game: &mut Game) -> Result<(), Error> {
         let id = game
             .id()
             .ok_or(eyre::eyre!("Trying to update game without id"))?;
+        let read_update_time = game
+            .update_time()
+            .ok_or(eyre::eyre!("Trying to update game without update time"))?;
+        let SummaryColumns {
+            abandon_reason,
+            abandon_time,
+            current_player_number,
+            current_points_to_win,
+            current_round_number,
+            winner,
+        } = game.state().into();
         let end_time = game.end_time();
         let players_number = game.players_number().value() as i32;
         let points_limit = game.points_limit().value() as i32;
         let rounds: Vec<RoundsColumnItem> = game.rounds().iter().map(Into::into).collect();
+        let ShotClockColumns {
+            late_visit_policy,
+            turn_time_limit_ms,
+        } = game.shot_clock().into();
         let start_time = game.start_time();
 
         let update_time = sqlx::query_file_scalar!(
             "queries/update_game.sql",
             id,
+            abandon_reason,
+            abandon_time,
+            current_player_number,
+            current_points_to_win,
+            current_round_number,
             end_time,
+            late_visit_policy,
             players_number,
             points_limit,
             Json(rounds) as _,
+            ROUNDS_COLUMN_VERSION,
             start_time,
+            turn_time_limit_ms,
+            winner,
+            read_update_time,
         )
-        .fetch_one(self)
+        .fetch_optional(&mut *self)
         .await
         .map_err(eyre::Report::new)?;
 
+        let Some(update_time) = update_time else {
+            if self.find_game(id).await?.is_some() {
+                return Err(Error::Aborted(
+                    Reason::GameChanged,
+                    format!("Game {id} was changed since it was read"),
+                ));
+            }
+
+            return Err(Error::NotFound(Reason::GameNotFound, format!("Game {id}")));
+        };
+
         game.change_update_time(update_time)?;
 
         Ok(())
     }
 }
+
+/// Game state denormalized into columns, so listings can skip the rounds replay.
+struct SummaryColumns {
+    abandon_reason: Option<String>,
+    abandon_time: Option<DateTime<Utc>>,
+    current_player_number: Option<i32>,
+    current_points_to_win: Option<i32>,
+    current_round_number: Option<i32>,
+    winner: Option<i32>,
+}
+
+impl From<&State> for SummaryColumns {
+    fn from(value: &State) -> Self {
+        match value {
+            State::NotStarted(state) => Self {
+                abandon_reason: None,
+                abandon_time: None,
+                current_player_number: Some(state.player_number().value() as i32),
+                current_points_to_win: Some(state.points_to_win().value().into()),
+                current_round_number: Some(state.round_number().value() as i32),
+                winner: None,
+            },
+            State::InProgress(state) => Self {
+                abandon_reason: None,
+                abandon_time: None,
+                current_player_number: Some(state.player_number().value() as i32),
+                current_points_to_win: Some(state.points_to_win().value().into()),
+                current_round_number: Some(state.round_number().value() as i32),
+                winner: None,
+            },
+            State::Finished(state) => Self {
+                abandon_reason: None,
+                abandon_time: None,
+                current_player_number: None,
+                current_points_to_win: None,
+                current_round_number: None,
+                winner: Some(state.winner().value() as i32),
+            },
+            State::Abandoned(state) => Self {
+                abandon_reason: Some(state.reason().to_string()),
+                abandon_time: Some(state.time()),
+                current_player_number: None,
+                current_points_to_win: None,
+                current_round_number: None,
+                winner: None,
+            },
+        }
+    }
+}
@@ -1,19 +1,98 @@
 use crate::{
-    game_row::{FindGame, InsertGame, ListGames, RoundsColumnItem, UpdateGame},
-    GameRow,
+    decode_rounds,
+    game_row::{DeleteGame, FindGame, InsertGame, ListGames, UpdateGame},
+    ArchivedGame, FindGameRequest, GameRequestRow, GameRow, GameSummaryRow, InsertGameRequest,
+    InsertScoreCorrection, ListGameSummaries, ListScoreCorrections, PointsKind, RoundsColumnItem,
+    ScoreCorrectionRow, ShotClockColumns, ROUNDS_COLUMN_VERSION,
 };
 use playground::{
-    coordinator, Error, Game, LoadGameParameters, NewRoundParameters, Number, PlayerScore, Points,
-    Round, Score,
+    coordinator, Error, Game, GameSummary, LoadGameParameters, LoadGameSummaryParameters,
+    NewRoundParameters, NewScoreCorrectionParameters, Number, PlayerScore, Points, Reason, Round,
+    Score, ScoreCorrection,
+};
+use sqlx::{
+    pool::PoolConnection, postgres::PgPoolOptions, Connection, PgConnection, PgPool, Postgres,
+    Transaction,
 };
-use sqlx::{pool::PoolConnection, postgres::PgPoolOptions, PgPool, Postgres};
 use uuid::Uuid;
 
-const POINTS_KIND_REGULAR: &str = "regular";
-const POINTS_KIND_OVERTHROW: &str = "overthrow";
-
+#[derive(Clone)]
 pub struct Repo {
-    pool: sqlx::Pool<sqlx::postgres::Postgres>,
+    pub(crate) pool: sqlx::Pool<sqlx::postgres::Postgres>,
+}
+
+/// Connection usage of the database pool.
+pub struct PoolStatus {
+    pub idle: usize,
+    pub max_size: u32,
+    pub size: u32,
+}
+
+pub struct ConnectParameters<'a> {
+    pub database_url: &'a str,
+    pub pool_size: u32,
+}
+
+impl coordinator::CorrectGame for Repo {
+    async fn correct_game(
+        &self,
+        game: &mut Game,
+        correction: &ScoreCorrection,
+        request_id: Option<&str>,
+    ) -> Result<(), Error> {
+        let game_id = game
+            .id()
+            .ok_or(eyre::eyre!("Trying to correct game without id"))?;
+        let mut transaction = self.transaction().await?;
+
+        transaction.update_game(game).await?;
+        transaction
+            .insert_score_correction(&ScoreCorrectionRow {
+                correct_time: correction.time(),
+                game_id,
+                player_number: correction.player_number().value() as i32,
+                points: correction.score().points().value().into(),
+                previous_points: correction.previous_score().points().value().into(),
+                round_number: correction.round_number().value() as i32,
+            })
+            .await?;
+
+        if let Some(request_id) = request_id {
+            record_request(&mut transaction, game, request_id).await?;
+        }
+
+        transaction.commit().await.map_err(eyre::Report::new)?;
+
+        Ok(())
+    }
+}
+
+impl coordinator::DeleteGame for Repo {
+    async fn delete_game(&self, id: Uuid, request_id: Option<&str>) -> Result<(), Error> {
+        let mut transaction = self.transaction().await?;
+
+        // The request keeps the game as it was last read.
+        let game: Option<Game> = match request_id {
+            Some(_) => transaction
+                .find_game(id)
+                .await?
+                .map(TryInto::try_into)
+                .transpose()?,
+            None => None,
+        };
+
+        if !transaction.delete_game(id).await? {
+            return Err(Error::NotFound(Reason::GameNotFound, format!("Game {id}")));
+        }
+
+        if let Some((game, request_id)) = game.zip(request_id) {
+            record_request(&mut transaction, &game, request_id).await?;
+        }
+
+        transaction.commit().await.map_err(eyre::Report::new)?;
+
+        Ok(())
+    }
 }
 
 impl coordinator::GetGame for Repo {
@@ -22,23 +101,52 @@ impl coordinator::GetGame for Repo {
             .await?
             .find_game(id)
             .await?
-            .ok_or(Error::NotFound(format!("Game {id}")))
+            .ok_or(Error::NotFound(Reason::GameNotFound, format!("Game {id}")))
             .map(TryInto::try_into)?
     }
 }
 
+impl coordinator::GetRequestedGame for Repo {
+    async fn get_requested_game(
+        &self,
+        request_id: &str,
+        game_id: Option<Uuid>,
+    ) -> Result<Option<Game>, Error> {
+        let Some(request) = self
+            .conn()
+            .await?
+            .find_game_request(request_id, game_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        requested_game(request).map(Some)
+    }
+}
+
 impl coordinator::InsertGame for Repo {
-    async fn insert_game(&self, game: &mut Game) -> Result<(), Error> {
-        self.conn().await?.insert_game(game).await
+    async fn insert_game(&self, game: &mut Game, request_id: Option<&str>) -> Result<(), Error> {
+        let mut transaction = self.transaction().await?;
+
+        transaction.insert_game(game).await?;
+
+        if let Some(request_id) = request_id {
+            record_request(&mut transaction, game, request_id).await?;
+        }
+
+        transaction.commit().await.map_err(eyre::Report::new)?;
+
+        Ok(())
     }
 }
 
 impl coordinator::ListGames for Repo {
-    async fn list_games(&self) -> Result<Vec<Game>, Error> {
+    async fn list_games(&self, include_deleted: bool) -> Result<Vec<Game>, Error> {
         let games = self
             .conn()
             .await?
-            .list_games()
+            .list_games(include_deleted)
             .await?
             .into_iter()
             .map(TryInto::try_into)
@@ -48,19 +156,74 @@ impl coordinator::ListGames for Repo {
     }
 }
 
+impl coordinator::ListGameSummaries for Repo {
+    async fn list_game_summaries(&self, include_deleted: bool) -> Result<Vec<GameSummary>, Error> {
+        let summaries = self
+            .conn()
+            .await?
+            .list_game_summaries(include_deleted)
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<GameSummary>, Error>>()?;
+
+        Ok(summaries)
+    }
+}
+
+impl coordinator::ListScoreCorrections for Repo {
+    async fn list_score_corrections(&self, game_id: Uuid) -> Result<Vec<ScoreCorrection>, Error> {
+        let corrections = self
+            .conn()
+            .await?
+            .list_score_corrections(game_id)
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<ScoreCorrection>, Error>>()?;
+
+        Ok(corrections)
+    }
+}
+
 impl coordinator::UpdateGame for Repo {
-    async fn update_game(&self, game: &mut Game) -> Result<(), Error> {
-        self.conn().await?.update_game(game).await
+    async fn update_game(&self, game: &mut Game, request_id: Option<&str>) -> Result<(), Error> {
+        let mut transaction = self.transaction().await?;
+
+        transaction.update_game(game).await?;
+
+        if let Some(request_id) = request_id {
+            record_request(&mut transaction, game, request_id).await?;
+        }
+
+        transaction.commit().await.map_err(eyre::Report::new)?;
+
+        Ok(())
     }
 }
 
 impl Repo {
-    async fn conn(&self) -> Result<PoolConnection<Postgres>, Error> {
+    pub(crate) async fn conn(&self) -> Result<PoolConnection<Postgres>, Error> {
         let conn = self.pool.acquire().await.map_err(eyre::Report::new)?;
 
         Ok(conn)
     }
 
+    pub async fn connect(parameters: ConnectParameters<'_>) -> Result<Self, Error> {
+        let ConnectParameters {
+            database_url,
+            pool_size,
+        } = parameters;
+
+        let pool = PgPoolOptions::new()
+            .max_connections(pool_size)
+            .connect(database_url)
+            .await
+            .map_err(eyre::Report::new)?;
+
+        Ok(Self::new(pool))
+    }
+
     pub async fn from_database_url(database_url: &str) -> Result<Self, Error> {
         let pool = PgPoolOptions::new()
             .connect(database_url)
@@ -73,6 +236,32 @@ impl Repo {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Checks that the database is reachable.
+    pub async fn ping(&self) -> Result<(), Error> {
+        self.conn().await?.ping().await.map_err(eyre::Report::new)?;
+
+        Ok(())
+    }
+
+    /// Waits for borrowed connections to be returned and closes all of them.
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    pub fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            idle: self.pool.num_idle(),
+            max_size: self.pool.options().get_max_connections(),
+            size: self.pool.size(),
+        }
+    }
+
+    pub(crate) async fn transaction(&self) -> Result<Transaction<'_, Postgres>, Error> {
+        let transaction = self.pool.begin().await.map_err(eyre::Report::new)?;
+
+        Ok(transaction)
+    }
 }
 
 impl TryFrom<GameRow> for Game {
@@ -80,45 +269,122 @@ impl TryFrom<GameRow> for Game {
 
     fn try_from(row: GameRow) -> Result<Self, Self::Error> {
         let GameRow {
+            abandon_reason,
+            abandon_time,
             end_time,
             id,
             insert_time,
+            late_visit_policy,
             players_number,
             points_limit,
             rounds,
+            rounds_version,
             start_time,
+            turn_time_limit_ms,
             update_time,
+            winner: _,
         } = row;
 
         let players_number = players_number.try_into().map_err(eyre::Report::new)?;
         let points_limit = points_limit.try_into().map_err(eyre::Report::new)?;
-        let rounds = rounds
+        let rounds = decode_rounds(rounds_version, rounds)?
             .iter()
             .map(TryInto::<Round>::try_into)
             .collect::<Result<Vec<Round>, Error>>()?;
+        let shot_clock = ShotClockColumns {
+            late_visit_policy,
+            turn_time_limit_ms,
+        }
+        .shot_clock()?;
 
         Game::load(LoadGameParameters {
+            abandon_reason,
+            abandon_time,
             create_time: insert_time,
             end_time,
             id,
             players_number: Number::new(players_number)?,
             points_limit: Points::new(points_limit),
             rounds,
+            shot_clock,
+            start_time,
+            update_time,
+        })
+    }
+}
+
+impl TryFrom<GameSummaryRow> for GameSummary {
+    type Error = Error;
+
+    fn try_from(row: GameSummaryRow) -> Result<Self, Self::Error> {
+        let GameSummaryRow {
+            abandon_reason,
+            abandon_time,
+            current_player_number,
+            current_points_to_win,
+            current_round_number,
+            end_time,
+            id,
+            insert_time,
+            players_number,
+            points_limit,
+            start_time,
+            update_time,
+            winner,
+        } = row;
+
+        GameSummary::load(LoadGameSummaryParameters {
+            abandon_reason,
+            abandon_time,
+            create_time: insert_time,
+            end_time,
+            id,
+            player_number: current_player_number.map(number).transpose()?,
+            players_number: number(players_number)?,
+            points_limit: points(points_limit)?,
+            points_to_win: current_points_to_win.map(points).transpose()?,
+            round_number: current_round_number.map(number).transpose()?,
             start_time,
             update_time,
+            winner: winner.map(number).transpose()?,
         })
     }
 }
 
+impl TryFrom<ScoreCorrectionRow> for ScoreCorrection {
+    type Error = Error;
+
+    fn try_from(row: ScoreCorrectionRow) -> Result<Self, Self::Error> {
+        let ScoreCorrectionRow {
+            correct_time,
+            game_id: _,
+            player_number,
+            points,
+            previous_points,
+            round_number,
+        } = row;
+
+        Ok(Self::new(NewScoreCorrectionParameters {
+            player_number: number(player_number)?,
+            previous_score: score(previous_points)?,
+            round_number: number(round_number)?,
+            score: score(points)?,
+            time: correct_time,
+        }))
+    }
+}
+
 impl TryFrom<&RoundsColumnItem> for Round {
     type Error = Error;
 
     fn try_from(value: &RoundsColumnItem) -> Result<Self, Self::Error> {
         let RoundsColumnItem {
+            late,
             round_number,
             player_number,
             points_kind,
             points,
+            time,
         } = value.clone();
 
         let round_number = round_number.try_into().map_err(eyre::Report::new)?;
@@ -126,39 +392,87 @@ impl TryFrom<&RoundsColumnItem> for Round {
         let player_score = player_score(points, points_kind)?;
 
         Ok(Self::new(NewRoundParameters {
+            late,
             number: Number::new(round_number)?,
             player_number: Number::new(player_number)?,
             player_score,
+            time,
         }))
     }
 }
 
-fn player_score(points: i32, points_kind: String) -> Result<PlayerScore, Error> {
-    let points = points.try_into().map_err(eyre::Report::new)?;
+fn number(value: i32) -> Result<Number, Error> {
+    let value = value.try_into().map_err(eyre::Report::new)?;
 
-    if points_kind == POINTS_KIND_REGULAR {
-        return Ok(PlayerScore::Regular(Score::new(points)?));
-    }
+    Number::new(value)
+}
 
-    if points_kind == POINTS_KIND_OVERTHROW {
-        return Ok(PlayerScore::Overthrow(Score::new(points)?));
-    }
+fn points(value: i32) -> Result<Points, Error> {
+    let value = value.try_into().map_err(eyre::Report::new)?;
 
-    Err(Error::Unexpected(eyre::eyre!("Invalid points kind")))
+    Ok(Points::new(value))
+}
+
+fn score(points: i32) -> Result<Score, Error> {
+    Score::new(points.try_into().map_err(eyre::Report::new)?)
+}
+
+fn player_score(points: i32, points_kind: PointsKind) -> Result<PlayerScore, Error> {
+    let score = score(points)?;
+
+    Ok(match points_kind {
+        PointsKind::Regular => PlayerScore::Regular(score),
+        PointsKind::Overthrow => PlayerScore::Overthrow(score),
+    })
 }
 
 impl From<&Round> for RoundsColumnItem {
     fn from(value: &Round) -> Self {
         let (points_kind, points) = match value.player_score() {
-            PlayerScore::Regular(score) => (POINTS_KIND_REGULAR, score.points().value().into()),
-            PlayerScore::Overthrow(score) => (POINTS_KIND_OVERTHROW, score.points().value().into()),
+            PlayerScore::Regular(score) => (PointsKind::Regular, score.points().value().into()),
+            PlayerScore::Overthrow(score) => (PointsKind::Overthrow, score.points().value().into()),
         };
 
         Self {
             round_number: value.number().value() as i32,
             player_number: value.player_number().value() as i32,
-            points_kind: points_kind.into(),
+            points_kind,
             points,
+            time: value.time(),
+            late: value.is_late(),
         }
     }
 }
+
+async fn record_request(
+    conn: &mut PgConnection,
+    game: &Game,
+    request_id: &str,
+) -> Result<(), Error> {
+    let archived = ArchivedGame::try_from(game)?;
+
+    let recorded = conn
+        .insert_game_request(&GameRequestRow {
+            game_id: archived.id,
+            game: serde_json::to_value(&archived).map_err(eyre::Report::new)?,
+            request_id: request_id.to_string(),
+            rounds_version: ROUNDS_COLUMN_VERSION,
+        })
+        .await?;
+
+    if !recorded {
+        return Err(Error::AlreadyExists(
+            Reason::DuplicateRequestId,
+            format!("Request {request_id}"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Game as the request left it, later changes of the game do not show.
+fn requested_game(request: GameRequestRow) -> Result<Game, Error> {
+    let game: ArchivedGame = serde_json::from_value(request.game).map_err(eyre::Report::new)?;
+
+    game.into_row(request.rounds_version).try_into()
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use playground::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version of the `rounds` column layout written by the current code.
+///
+/// Rows keep the version they were written with and are upgraded to the
+/// current layout the next time the game is updated. When changing
+/// [`RoundsColumnItem`], freeze its current shape under a versioned name,
+/// bump this constant and add an upgrade step to [`decode_rounds`].
+pub const ROUNDS_COLUMN_VERSION: i32 = 3;
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct RoundsColumnItem {
+    pub round_number: i32,
+    pub player_number: i32,
+    pub points_kind: PointsKind,
+    pub points: i32,
+    /// Absent for visits counted before times were kept.
+    pub time: Option<DateTime<Utc>>,
+    /// Counted after the shot clock of its turn ran out.
+    pub late: bool,
+}
+
+/// Layout of version 2, visits without shot clock flags.
+#[derive(Deserialize)]
+struct RoundsColumnItemV2 {
+    round_number: i32,
+    player_number: i32,
+    points_kind: PointsKind,
+    points: i32,
+    time: Option<DateTime<Utc>>,
+}
+
+/// Layout of version 1, visits without times.
+#[derive(Deserialize)]
+struct RoundsColumnItemV1 {
+    round_number: i32,
+    player_number: i32,
+    points_kind: PointsKind,
+    points: i32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PointsKind {
+    Regular,
+    Overthrow,
+}
+
+impl PointsKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PointsKind::Regular => "regular",
+            PointsKind::Overthrow => "overthrow",
+        }
+    }
+}
+
+/// Reads `rounds` column written with any known version in the current layout.
+pub fn decode_rounds(version: i32, rounds: Value) -> Result<Vec<RoundsColumnItem>, Error> {
+    match version {
+        1 => {
+            let items: Vec<RoundsColumnItemV1> =
+                serde_json::from_value(rounds).map_err(eyre::Report::new)?;
+
+            Ok(items
+                .into_iter()
+                .map(RoundsColumnItemV2::from)
+                .map(Into::into)
+                .collect())
+        }
+        2 => {
+            let items: Vec<RoundsColumnItemV2> =
+                serde_json::from_value(rounds).map_err(eyre::Report::new)?;
+
+            Ok(items.into_iter().map(Into::into).collect())
+        }
+        3 => Ok(serde_json::from_value(rounds).map_err(eyre::Report::new)?),
+        version => Err(Error::Unexpected(eyre::eyre!(
+            "Unknown rounds column version: {version}"
+        ))),
+    }
+}
+
+impl From<RoundsColumnItemV1> for RoundsColumnItemV2 {
+    fn from(item: RoundsColumnItemV1) -> Self {
+        Self {
+            round_number: item.round_number,
+            player_number: item.player_number,
+            points_kind: item.points_kind,
+            points: item.points,
+            time: None,
+        }
+    }
+}
+
+impl From<RoundsColumnItemV2> for RoundsColumnItem {
+    fn from(item: RoundsColumnItemV2) -> Self {
+        Self {
+            round_number: item.round_number,
+            player_number: item.player_number,
+            points_kind: item.points_kind,
+            points: item.points,
+            time: item.time,
+            late: false,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use playground::Error;
+use uuid::Uuid;
+
+pub trait InsertScoreCorrection {
+    async fn insert_score_correction(&mut self, row: &ScoreCorrectionRow) -> Result<(), Error>;
+}
+
+pub trait ListScoreCorrections {
+    async fn list_score_corrections(
+        &mut self,
+        game_id: Uuid,
+    ) -> Result<Vec<ScoreCorrectionRow>, Error>;
+}
+
+pub struct ScoreCorrectionRow {
+    pub correct_time: DateTime<Utc>,
+    pub game_id: Uuid,
+    pub player_number: i32,
+    pub points: i32,
+    pub previous_points: i32,
+    pub round_number: i32,
+}
@@ -0,0 +1,107 @@
+use crate::{GameRow, QuarantineGame, Repo, ScanGames};
+use chrono::{DateTime, SubsecRound, Utc};
+use playground::{Error, Game, Round};
+use uuid::Uuid;
+
+pub struct GameIssue {
+    pub description: String,
+    pub game_id: Uuid,
+}
+
+pub struct VerificationReport {
+    pub games_number: usize,
+    pub issues: Vec<GameIssue>,
+}
+
+pub struct VerifyGamesParameters {
+    /// Mark broken games so that listings skip them.
+    pub quarantine: bool,
+}
+
+impl Repo {
+    /// Replays every stored game and compares the outcome with stored columns.
+    pub async fn verify_games(
+        &self,
+        parameters: VerifyGamesParameters,
+    ) -> Result<VerificationReport, Error> {
+        let VerifyGamesParameters { quarantine } = parameters;
+
+        let mut conn = self.conn().await?;
+        let rows = conn.scan_games().await?;
+        let games_number = rows.len();
+        let mut issues = Vec::new();
+
+        for row in rows {
+            let game_id = row.id;
+
+            let Err(description) = verify_game_row(row) else {
+                continue;
+            };
+
+            if quarantine {
+                conn.quarantine_game(game_id, &description).await?;
+            }
+
+            issues.push(GameIssue {
+                description,
+                game_id,
+            });
+        }
+
+        Ok(VerificationReport {
+            games_number,
+            issues,
+        })
+    }
+}
+
+fn verify_game_row(row: GameRow) -> Result<(), String> {
+    let start_time = row.start_time;
+    let end_time = row.end_time;
+    let winner = row.winner;
+
+    let game: Game = row
+        .try_into()
+        .map_err(|err: Error| format!("Replay failed: {err}"))?;
+
+    // Loading keeps the stored times, the replayed ones are those of the first and the
+    // winning visit. Visits counted before times were kept can only be checked for presence.
+    let rounds = game.rounds();
+    let replayed_start_time = game
+        .start_time()
+        .map(|time| rounds.first().and_then(Round::time).unwrap_or(time));
+    let replayed_end_time = game
+        .end_time()
+        .map(|time| rounds.last().and_then(Round::time).unwrap_or(time));
+
+    verify_time("Start", start_time, replayed_start_time)?;
+    verify_time("End", end_time, replayed_end_time)?;
+
+    let replayed_winner = game.winner().map(|number| number.value() as i32);
+
+    if replayed_winner != winner {
+        return Err(format!(
+            "Winner mismatch. Stored: {:?}, replayed: {:?}",
+            winner, replayed_winner
+        ));
+    }
+
+    Ok(())
+}
+
+/// Visit times are kept to the microsecond, as are the timestamp columns.
+fn verify_time(
+    name: &str,
+    stored: Option<DateTime<Utc>>,
+    replayed: Option<DateTime<Utc>>,
+) -> Result<(), String> {
+    let replayed = replayed.map(|time| time.trunc_subsecs(6));
+
+    if replayed != stored {
+        return Err(format!(
+            "{name} time mismatch. Stored: {stored:?}, replayed: {replayed:?}"
+        ));
+    }
+
+    Ok(())
+}
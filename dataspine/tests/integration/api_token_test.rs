@@ -0,0 +1,33 @@
+use dataspine::{secret_hash, IssueApiTokenParameters, Repo, Role};
+use playground::{Error, Reason};
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn it_issues_and_revokes_api_tokens(pool: PgPool) -> anyhow::Result<()> {
+    let repo = Repo::new(pool);
+
+    let issued = repo
+        .issue_api_token(IssueApiTokenParameters {
+            name: "scoreboard",
+            role: Role::Scorer,
+        })
+        .await?;
+
+    assert_eq!(issued.api_token.secret_hash, secret_hash(&issued.secret));
+
+    repo.revoke_api_token(issued.api_token.id).await?;
+    let api_tokens = repo.list_api_tokens().await?;
+
+    assert_eq!(api_tokens.len(), 1);
+    assert_eq!(api_tokens[0].role, Role::Scorer);
+    assert!(api_tokens[0].revoke_time.is_some());
+
+    let result = repo.revoke_api_token(issued.api_token.id).await;
+
+    assert!(matches!(
+        result,
+        Err(Error::NotFound(Reason::ApiTokenNotFound, _))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::helpers;
+use dataspine::{GameArchive, Repo};
+use sqlx::PgPool;
+
+#[sqlx::test(fixtures("games"))]
+async fn it_exports_and_imports_games(pool: PgPool) -> anyhow::Result<()> {
+    let repo = Repo::new(pool.clone());
+
+    let mut json = Vec::new();
+    repo.export_games().await?.write_json(&mut json)?;
+
+    sqlx::query("DELETE FROM playground.games")
+        .execute(&pool)
+        .await?;
+    let report = repo
+        .import_games(GameArchive::read_json(&json[..])?)
+        .await?;
+
+    assert_eq!(report.imported, 1);
+    assert_eq!(helpers::count_games(&pool).await?, 1);
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_skips_existing_games_on_import(pool: PgPool) -> anyhow::Result<()> {
+    let repo = Repo::new(pool);
+
+    let archive = repo.export_games().await?;
+    let report = repo.import_games(archive).await?;
+
+    assert_eq!(report.imported, 0);
+    assert_eq!(report.skipped, 1);
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_exports_visits_to_csv(pool: PgPool) -> anyhow::Result<()> {
+    let mut csv = Vec::new();
+    Repo::new(pool).export_games().await?.write_csv(&mut csv)?;
+
+    assert_eq!(String::from_utf8(csv)?.lines().count(), 4);
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_does_not_import_invalid_games(pool: PgPool) -> anyhow::Result<()> {
+    let repo = Repo::new(pool.clone());
+
+    let mut archive = repo.export_games().await?;
+    sqlx::query("DELETE FROM playground.games")
+        .execute(&pool)
+        .await?;
+    archive.games[0].players_number = 0;
+    let result = repo.import_games(archive).await;
+
+    assert!(result.is_err());
+    assert_eq!(helpers::count_games(&pool).await?, 0);
+
+    Ok(())
+}
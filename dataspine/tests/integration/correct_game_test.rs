@@ -0,0 +1,31 @@
+use crate::helpers;
+use dataspine::Repo;
+use playground::{
+    coordinator::{CorrectGame, GetGame, ListScoreCorrections},
+    Number, Score,
+};
+use sqlx::PgPool;
+
+#[sqlx::test(fixtures("games"))]
+async fn it_corrects_game(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut game = repo.get_game(game_id).await?;
+    let correction = game.correct_score(Number::one(), Number::new(2)?, Score::new(40)?)?;
+    repo.correct_game(&mut game, &correction, None).await?;
+
+    let game = repo.get_game(game_id).await?;
+    let corrections = repo.list_score_corrections(game_id).await?;
+    let round = game
+        .rounds()
+        .iter()
+        .find(|round| round.player_number() == Number::new(2).unwrap())
+        .map(|round| round.player_score().score().points().value());
+
+    assert_eq!(round, Some(40));
+    assert_eq!(corrections.len(), 1);
+    assert_eq!(corrections[0].previous_score().points().value(), 24);
+
+    Ok(())
+}
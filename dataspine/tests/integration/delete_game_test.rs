@@ -0,0 +1,88 @@
+use crate::helpers;
+use dataspine::Repo;
+use playground::coordinator::{
+    DeleteGame, GetGame, GetRequestedGame, ListGameSummaries, ListGames, UpdateGame,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[sqlx::test(fixtures("games"))]
+async fn it_deletes_game(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool.clone());
+
+    repo.delete_game(game_id, None).await?;
+
+    assert!(repo.get_game(game_id).await.is_err());
+    assert!(repo.list_games(false).await?.is_empty());
+    assert!(repo.list_game_summaries(false).await?.is_empty());
+    assert_eq!(helpers::count_games(&pool).await?, 1);
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_lists_deleted_game_on_request(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    repo.delete_game(game_id, None).await?;
+    let games = repo.list_games(true).await?;
+    let summaries = repo.list_game_summaries(true).await?;
+
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].id(), Some(game_id));
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].id(), game_id);
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_does_not_delete_game_twice(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    repo.delete_game(game_id, None).await?;
+    let result = repo.delete_game(game_id, None).await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_records_delete_request(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    repo.delete_game(game_id, Some("delete")).await?;
+    let requested = repo.get_requested_game("delete", Some(game_id)).await?;
+
+    assert_eq!(requested.and_then(|game| game.id()), Some(game_id));
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_does_not_delete_unknown_game(pool: PgPool) -> anyhow::Result<()> {
+    let result = Repo::new(pool).delete_game(Uuid::nil(), None).await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_does_not_update_deleted_game(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut game = repo.get_game(game_id).await?;
+    repo.delete_game(game_id, None).await?;
+    let result = repo.update_game(&mut game, None).await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
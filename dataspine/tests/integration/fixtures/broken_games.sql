@@ -0,0 +1,11 @@
+INSERT INTO playground.games (
+    players_number, points_limit, rounds, rounds_version, start_time
+) VALUES (
+    2,
+    301,
+    '[
+        {"points": 17, "points_kind": "unknown", "round_number": 1, "player_number": 1}
+    ]'::jsonb,
+    1,
+    NOW()
+)
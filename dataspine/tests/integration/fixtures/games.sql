@@ -1,6 +1,16 @@
 INSERT INTO playground.games (
-    players_number, points_limit, rounds, start_time
+    current_player_number,
+    current_points_to_win,
+    current_round_number,
+    players_number,
+    points_limit,
+    rounds,
+    rounds_version,
+    start_time
 ) VALUES (
+    2,
+    277,
+    2,
     2,
     301,
     '[
@@ -8,5 +18,6 @@ INSERT INTO playground.games (
         {"points": 24, "points_kind": "regular", "round_number": 1, "player_number": 2},
         {"points": 27, "points_kind": "regular", "round_number": 2, "player_number": 1}
     ]'::jsonb,
+    1,
     NOW()
 )
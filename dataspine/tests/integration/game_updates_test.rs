@@ -0,0 +1,18 @@
+use crate::helpers;
+use dataspine::Repo;
+use playground::coordinator::{GetGame, UpdateGame};
+use sqlx::PgPool;
+
+#[sqlx::test(fixtures("games"))]
+async fn it_notifies_about_game_update(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut updates = repo.listen_game_updates().await?;
+    let mut game = repo.get_game(game_id).await?;
+    repo.update_game(&mut game, None).await?;
+
+    assert_eq!(updates.recv().await?, game_id);
+
+    Ok(())
+}
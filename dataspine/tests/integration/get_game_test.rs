@@ -1,6 +1,6 @@
 use crate::helpers;
 use dataspine::Repo;
-use playground::coordinator::GetGame;
+use playground::{coordinator::GetGame, Round};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -15,6 +15,20 @@ async fn it_gets_game(pool: PgPool) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(fixtures("games"))]
+async fn it_gets_game_with_visits_without_times(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+
+    let game = Repo::new(pool).get_game(game_id).await?;
+
+    let times: Vec<_> = game.rounds().iter().map(Round::time).collect();
+
+    // The first visit is taken to have started the game.
+    assert_eq!(times, vec![game.start_time(), None, None]);
+
+    Ok(())
+}
+
 #[sqlx::test(fixtures("games"))]
 async fn it_does_not_get_game(pool: PgPool) -> anyhow::Result<()> {
     let result = Repo::new(pool).get_game(Uuid::nil()).await;
@@ -23,3 +37,17 @@ async fn it_does_not_get_game(pool: PgPool) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx::test(fixtures("games"))]
+async fn it_does_not_get_game_with_unknown_rounds_version(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+
+    sqlx::query("UPDATE playground.games SET rounds_version = 0")
+        .execute(&pool)
+        .await?;
+    let result = Repo::new(pool).get_game(game_id).await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
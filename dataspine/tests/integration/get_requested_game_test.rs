@@ -0,0 +1,108 @@
+use crate::helpers;
+use dataspine::Repo;
+use playground::{
+    coordinator::{CorrectGame, GetGame, GetRequestedGame, InsertGame, UpdateGame},
+    Game, NewGameParameters, Number, Points, Reason, Score,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[sqlx::test(fixtures("games"))]
+async fn it_gets_game_as_request_left_it(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut game = repo.get_game(game_id).await?;
+    game.count_score(Score::new(60)?)?;
+    repo.update_game(&mut game, Some("first")).await?;
+    let update_time = game.update_time();
+    game.count_score(Score::new(20)?)?;
+    repo.update_game(&mut game, None).await?;
+
+    let requested = repo
+        .get_requested_game("first", Some(game_id))
+        .await?
+        .unwrap();
+
+    assert_eq!(requested.rounds().len(), 4);
+    assert_eq!(requested.update_time(), update_time);
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_gets_game_as_request_left_it_after_correction(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut game = repo.get_game(game_id).await?;
+    game.count_score(Score::new(60)?)?;
+    repo.update_game(&mut game, Some("first")).await?;
+    let correction = game.correct_score(Number::one(), Number::one(), Score::new(20)?)?;
+    repo.correct_game(&mut game, &correction, None).await?;
+
+    let requested = repo
+        .get_requested_game("first", Some(game_id))
+        .await?
+        .unwrap();
+    let points: Vec<u16> = requested
+        .rounds()
+        .iter()
+        .map(|round| round.player_score().score().points().value())
+        .collect();
+
+    assert_eq!(points, [17, 24, 27, 60]);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn it_gets_created_game(pool: PgPool) -> anyhow::Result<()> {
+    let repo = Repo::new(pool);
+    let mut game = Game::new(NewGameParameters {
+        points_limit: Points::new(301),
+        players_number: Number::one(),
+        shot_clock: None,
+    })?;
+
+    repo.insert_game(&mut game, Some("create")).await?;
+    let requested = repo.get_requested_game("create", None).await?.unwrap();
+
+    assert_eq!(requested.id(), game.id());
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_does_not_get_request_of_other_game(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut game = repo.get_game(game_id).await?;
+    repo.update_game(&mut game, Some("first")).await?;
+
+    let requested = repo.get_requested_game("first", Some(Uuid::nil())).await?;
+
+    assert!(requested.is_none());
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_does_not_record_request_twice(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut game = repo.get_game(game_id).await?;
+    repo.update_game(&mut game, Some("first")).await?;
+    game.count_score(Score::new(60)?)?;
+    let result = repo.update_game(&mut game, Some("first")).await;
+
+    assert_eq!(
+        result.err().and_then(|err| err.reason()),
+        Some(Reason::DuplicateRequestId)
+    );
+    assert_eq!(repo.get_game(game_id).await?.rounds().len(), 3);
+
+    Ok(())
+}
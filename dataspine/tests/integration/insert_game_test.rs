@@ -1,6 +1,10 @@
 use crate::helpers;
+use chrono::Duration;
 use dataspine::Repo;
-use playground::{coordinator::InsertGame, Game, NewGameParameters, Number, Points};
+use playground::{
+    coordinator::{GetGame, InsertGame},
+    Game, LateVisitPolicy, NewGameParameters, NewShotClockParameters, Number, Points, ShotClock,
+};
 use sqlx::PgPool;
 
 #[sqlx::test]
@@ -9,9 +13,10 @@ async fn it_saves_game(pool: PgPool) -> anyhow::Result<()> {
     let mut game = Game::new(NewGameParameters {
         points_limit: Points::new(301),
         players_number: Number::one(),
+        shot_clock: None,
     })?;
 
-    Repo::new(pool.clone()).insert_game(&mut game).await?;
+    Repo::new(pool.clone()).insert_game(&mut game, None).await?;
     let count_games_now = helpers::count_games(&pool).await?;
 
     assert!(game.id().is_some());
@@ -22,3 +27,25 @@ async fn it_saves_game(pool: PgPool) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx::test]
+async fn it_saves_game_with_shot_clock(pool: PgPool) -> anyhow::Result<()> {
+    let repo = Repo::new(pool);
+    let mut game = Game::new(NewGameParameters {
+        points_limit: Points::new(301),
+        players_number: Number::one(),
+        shot_clock: Some(ShotClock::new(NewShotClockParameters {
+            late_visit_policy: LateVisitPolicy::ScoreZero,
+            turn_time_limit: Duration::seconds(30),
+        })?),
+    })?;
+
+    repo.insert_game(&mut game, None).await?;
+    let game = repo.get_game(game.id().unwrap()).await?;
+    let shot_clock = game.shot_clock();
+
+    assert!(shot_clock.is_some_and(|clock| clock.late_visit_policy() == LateVisitPolicy::ScoreZero));
+    assert!(shot_clock.is_some_and(|clock| clock.turn_time_limit() == Duration::seconds(30)));
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use dataspine::Repo;
+use playground::{coordinator::ListGameSummaries, State};
+use sqlx::PgPool;
+
+#[sqlx::test(fixtures("games"))]
+async fn it_lists_game_summaries(pool: PgPool) -> anyhow::Result<()> {
+    let summaries = Repo::new(pool).list_game_summaries(false).await?;
+
+    assert_eq!(summaries.len(), 1);
+
+    let State::InProgress(state) = summaries[0].state() else {
+        panic!("Game should be in progress");
+    };
+
+    assert_eq!(state.player_number().value(), 2);
+    assert_eq!(state.points_to_win().value(), 277);
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use sqlx::PgPool;
 
 #[sqlx::test(fixtures("games"))]
 async fn it_lists_games(pool: PgPool) -> anyhow::Result<()> {
-    let games = Repo::new(pool).list_games().await?;
+    let games = Repo::new(pool).list_games(false).await?;
 
     assert_eq!(games.len(), 1);
 
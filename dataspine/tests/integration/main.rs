@@ -1,5 +1,13 @@
+pub mod api_token_test;
+pub mod archive_test;
+pub mod correct_game_test;
+pub mod delete_game_test;
+pub mod game_updates_test;
 pub mod get_game_test;
+pub mod get_requested_game_test;
 pub mod helpers;
 pub mod insert_game_test;
+pub mod list_game_summaries_test;
 pub mod list_games_test;
 pub mod update_game_test;
+pub mod verify_games_test;
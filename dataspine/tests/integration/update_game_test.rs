@@ -1,6 +1,9 @@
 use crate::helpers;
 use dataspine::Repo;
-use playground::coordinator::{GetGame, UpdateGame};
+use playground::{
+    coordinator::{GetGame, ListGameSummaries, UpdateGame},
+    Error, Reason, Score, State,
+};
 use sqlx::PgPool;
 
 #[sqlx::test(fixtures("games"))]
@@ -10,10 +13,55 @@ async fn it_updates_game(pool: PgPool) -> anyhow::Result<()> {
     let mut game = Repo::new(pool.clone()).get_game(game_id).await?;
     let update_time_was = game.update_time();
 
-    Repo::new(pool).update_game(&mut game).await?;
+    Repo::new(pool).update_game(&mut game, None).await?;
     let update_time_now = game.update_time();
 
     assert!(update_time_was < update_time_now);
 
     Ok(())
 }
+
+#[sqlx::test(fixtures("games"))]
+async fn it_does_not_update_game_changed_since_read(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut first = repo.get_game(game_id).await?;
+    let mut second = repo.get_game(game_id).await?;
+    first.count_score(Score::new(20)?)?;
+    second.count_score(Score::new(40)?)?;
+
+    repo.update_game(&mut first, None).await?;
+    let result = repo.update_game(&mut second, None).await;
+
+    assert!(matches!(
+        result,
+        Err(Error::Aborted(Reason::GameChanged, _))
+    ));
+    assert_eq!(repo.get_game(game_id).await?.rounds().len(), 4);
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games"))]
+async fn it_updates_abandoned_game(pool: PgPool) -> anyhow::Result<()> {
+    let game_id = helpers::get_game_id(&pool).await?;
+    let repo = Repo::new(pool);
+
+    let mut game = repo.get_game(game_id).await?;
+    game.abandon("Power cut")?;
+    repo.update_game(&mut game, None).await?;
+
+    let game = repo.get_game(game_id).await?;
+    let summary = repo
+        .list_game_summaries(false)
+        .await?
+        .into_iter()
+        .find(|summary| summary.id() == game_id);
+
+    assert!(matches!(game.state(), State::Abandoned(state) if state.reason() == "Power cut"));
+    assert_eq!(game.rounds().len(), 3);
+    assert!(summary.is_some_and(|summary| matches!(summary.state(), State::Abandoned(_))));
+
+    Ok(())
+}
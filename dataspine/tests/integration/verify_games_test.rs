@@ -0,0 +1,66 @@
+use dataspine::{Repo, VerifyGamesParameters};
+use playground::{
+    coordinator::{InsertGame, ListGames},
+    Game, NewGameParameters, Number, Points, Score,
+};
+use sqlx::PgPool;
+
+#[sqlx::test(fixtures("games", "broken_games"))]
+async fn it_reports_broken_games(pool: PgPool) -> anyhow::Result<()> {
+    let report = Repo::new(pool)
+        .verify_games(VerifyGamesParameters { quarantine: false })
+        .await?;
+
+    assert_eq!(report.games_number, 2);
+    assert_eq!(report.issues.len(), 1);
+
+    Ok(())
+}
+
+#[sqlx::test(fixtures("games", "broken_games"))]
+async fn it_quarantines_broken_games(pool: PgPool) -> anyhow::Result<()> {
+    let repo = Repo::new(pool);
+
+    assert!(repo.list_games(false).await.is_err());
+
+    repo.verify_games(VerifyGamesParameters { quarantine: true })
+        .await?;
+    let games = repo.list_games(false).await?;
+
+    assert_eq!(games.len(), 1);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn it_reports_start_time_not_matching_first_visit(pool: PgPool) -> anyhow::Result<()> {
+    let repo = Repo::new(pool.clone());
+    let mut game = Game::new(NewGameParameters {
+        points_limit: Points::new(301),
+        players_number: Number::one(),
+        shot_clock: None,
+    })?;
+
+    game.count_score(Score::new(60)?)?;
+    repo.insert_game(&mut game, None).await?;
+
+    let report = repo
+        .verify_games(VerifyGamesParameters { quarantine: false })
+        .await?;
+
+    assert!(report.issues.is_empty());
+
+    sqlx::query("UPDATE playground.games SET start_time = start_time - INTERVAL '1 minute'")
+        .execute(&pool)
+        .await?;
+    let report = repo
+        .verify_games(VerifyGamesParameters { quarantine: false })
+        .await?;
+
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.issues[0]
+        .description
+        .starts_with("Start time mismatch"));
+
+    Ok(())
+}
@@ -1,35 +1,130 @@
-use crate::{Error, Game, NewGameParameters, Number, Points, Score};
+use crate::{
+    Error, ExpectedTurn, Game, GameSummary, NewGameParameters, Number, Points, Score,
+    ScoreCorrection, ShotClock, TimelineStep,
+};
 use uuid::Uuid;
 
+pub trait CorrectGame {
+    /// Saves the corrected game along with the correction. Records `request_id` as
+    /// [`UpdateGame`] does.
+    #[allow(async_fn_in_trait)]
+    async fn correct_game(
+        &self,
+        game: &mut Game,
+        correction: &ScoreCorrection,
+        request_id: Option<&str>,
+    ) -> Result<(), Error>;
+}
+
+pub trait DeleteGame {
+    /// Hides the game from reads and listings, its data is kept. Records `request_id`
+    /// as [`UpdateGame`] does.
+    #[allow(async_fn_in_trait)]
+    async fn delete_game(&self, game_id: Uuid, request_id: Option<&str>) -> Result<(), Error>;
+}
+
 pub trait GetGame {
     #[allow(async_fn_in_trait)]
     async fn get_game(&self, game_id: Uuid) -> Result<Game, Error>;
 }
 
+pub trait GetRequestedGame {
+    /// Game as it was right after the request with `request_id` changed it.
+    /// With `game_id`, requests that changed other games are not found.
+    #[allow(async_fn_in_trait)]
+    async fn get_requested_game(
+        &self,
+        request_id: &str,
+        game_id: Option<Uuid>,
+    ) -> Result<Option<Game>, Error>;
+}
+
 pub trait InsertGame {
+    /// Records `request_id` along with the game, failing when it is already recorded.
     #[allow(async_fn_in_trait)]
-    async fn insert_game(&self, game: &mut Game) -> Result<(), Error>;
+    async fn insert_game(&self, game: &mut Game, request_id: Option<&str>) -> Result<(), Error>;
 }
 
 pub trait ListGames {
     #[allow(async_fn_in_trait)]
-    async fn list_games(&self) -> Result<Vec<Game>, Error>;
+    async fn list_games(&self, include_deleted: bool) -> Result<Vec<Game>, Error>;
+}
+
+pub trait ListGameSummaries {
+    #[allow(async_fn_in_trait)]
+    async fn list_game_summaries(&self, include_deleted: bool) -> Result<Vec<GameSummary>, Error>;
+}
+
+pub trait ListScoreCorrections {
+    /// Corrections of the game, oldest first.
+    #[allow(async_fn_in_trait)]
+    async fn list_score_corrections(&self, game_id: Uuid) -> Result<Vec<ScoreCorrection>, Error>;
 }
 
 pub trait UpdateGame {
+    /// Records `request_id` along with the change, failing when it is already recorded.
+    /// Fails with [`Error::Aborted`] when the game was saved since it was read, so
+    /// concurrent changes are retried rather than overwritten.
     #[allow(async_fn_in_trait)]
-    async fn update_game(&self, game: &mut Game) -> Result<(), Error>;
+    async fn update_game(&self, game: &mut Game, request_id: Option<&str>) -> Result<(), Error>;
+}
+
+pub struct AbandonGameParameters<'a, G>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
+{
+    pub game_id: Uuid,
+    pub games: &'a G,
+    pub reason: String,
+    /// Client chosen key, a repeated request returns the game as the first one left it.
+    pub request_id: Option<&'a str>,
+}
+
+pub struct CancelLastScoreParameters<'a, G>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
+{
+    pub game_id: Uuid,
+    pub games: &'a G,
+    /// Client chosen key, a repeated request returns the game as the first one left it.
+    pub request_id: Option<&'a str>,
+}
+
+pub struct CorrectScoreParameters<'a, G>
+where
+    G: CorrectGame + GetGame + GetRequestedGame,
+{
+    pub game_id: Uuid,
+    pub games: &'a G,
+    pub player_number: Number,
+    /// Client chosen key, a repeated request returns the game as the first one left it.
+    pub request_id: Option<&'a str>,
+    pub round_number: Number,
+    pub score: Score,
 }
 
 pub struct CountScoreParameters<'a, G>
 where
-    G: GetGame + UpdateGame,
+    G: GetGame + GetRequestedGame + UpdateGame,
 {
+    pub expected_turn: ExpectedTurn,
     pub game_id: Uuid,
     pub games: &'a G,
+    /// Client chosen key, a repeated request returns the game as the first one left it.
+    pub request_id: Option<&'a str>,
     pub score: Score,
 }
 
+pub struct DeleteGameParameters<'a, G>
+where
+    G: DeleteGame + GetRequestedGame,
+{
+    pub game_id: Uuid,
+    pub games: &'a G,
+    /// Client chosen key, a repeated request succeeds without deleting anything.
+    pub request_id: Option<&'a str>,
+}
+
 pub struct GetGameParameters<'a, G>
 where
     G: GetGame,
@@ -38,13 +133,24 @@ where
     pub games: &'a G,
 }
 
+pub struct GetGameTimelineParameters<'a, G>
+where
+    G: GetGame,
+{
+    pub game_id: Uuid,
+    pub games: &'a G,
+}
+
 pub struct InitializeGameParameters<'a, G>
 where
-    G: InsertGame,
+    G: GetRequestedGame + InsertGame,
 {
     pub games: &'a G,
     pub players_number: Number,
     pub points_limit: Points,
+    /// Client chosen key, a repeated request returns the game created by the first one.
+    pub request_id: Option<&'a str>,
+    pub shot_clock: Option<ShotClock>,
 }
 
 pub struct ListGamesParameters<'a, G>
@@ -52,46 +158,296 @@ where
     G: ListGames,
 {
     pub games: &'a G,
+    /// Deleted games are left out unless this is set.
+    pub include_deleted: bool,
 }
 
-pub async fn count_score<G>(parameters: CountScoreParameters<'_, G>) -> Result<Game, Error>
+pub struct ListGameSummariesParameters<'a, G>
 where
-    G: GetGame + UpdateGame,
+    G: ListGameSummaries,
+{
+    pub games: &'a G,
+    /// Deleted games are left out unless this is set.
+    pub include_deleted: bool,
+}
+
+pub struct ListScoreCorrectionsParameters<'a, G>
+where
+    G: GetGame + ListScoreCorrections,
+{
+    pub game_id: Uuid,
+    pub games: &'a G,
+}
+
+pub struct SubmitVisitsParameters<'a, G>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
+{
+    /// Turn of the first visit.
+    pub expected_turn: ExpectedTurn,
+    pub game_id: Uuid,
+    pub games: &'a G,
+    /// Client chosen key, a repeated request returns the game as the first one left it.
+    pub request_id: Option<&'a str>,
+    /// Scores of consecutive visits, counted in order.
+    pub scores: Vec<Score>,
+}
+
+/// Settings to change, `None` keeps the current one.
+pub struct UpdateGameSettingsParameters<'a, G>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
+{
+    pub game_id: Uuid,
+    pub games: &'a G,
+    pub players_number: Option<Number>,
+    pub points_limit: Option<Points>,
+    /// Client chosen key, a repeated request returns the game as the first one left it.
+    pub request_id: Option<&'a str>,
+    /// `Some(None)` turns the shot clock off.
+    pub shot_clock: Option<Option<ShotClock>>,
+}
+
+/// Game left by a request with a request id, telling a repeated request from the one
+/// that changed the game.
+pub enum Requested {
+    Applied(Game),
+    /// Applied, and it was this request that finished the game.
+    Finished(Game),
+    /// The request was applied before, the game is as that one left it.
+    Replayed(Game),
+}
+
+pub enum SubmitVisitsError {
+    /// Visit at `index` could not be counted, so none of the batch was.
+    Visit {
+        error: Error,
+        index: usize,
+    },
+    Other(Error),
+}
+
+impl From<Error> for SubmitVisitsError {
+    fn from(error: Error) -> Self {
+        Self::Other(error)
+    }
+}
+
+impl Requested {
+    fn applied(game: Game, was_finished: bool) -> Self {
+        if game.winner().is_some() && !was_finished {
+            Requested::Finished(game)
+        } else {
+            Requested::Applied(game)
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        match self {
+            Requested::Applied(game) | Requested::Finished(game) | Requested::Replayed(game) => {
+                game
+            }
+        }
+    }
+
+    pub fn into_game(self) -> Game {
+        match self {
+            Requested::Applied(game) | Requested::Finished(game) | Requested::Replayed(game) => {
+                game
+            }
+        }
+    }
+
+    pub fn is_applied(&self) -> bool {
+        matches!(self, Requested::Applied(_) | Requested::Finished(_))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Requested::Finished(_))
+    }
+}
+
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id, request_id = parameters.request_id))]
+pub async fn abandon_game<G>(parameters: AbandonGameParameters<'_, G>) -> Result<Game, Error>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
+{
+    let AbandonGameParameters {
+        game_id,
+        games,
+        reason,
+        request_id,
+    } = parameters;
+
+    if let Some(request_id) = request_id {
+        if let Some(game) = games.get_requested_game(request_id, Some(game_id)).await? {
+            tracing::info!("Request already applied");
+
+            return Ok(game);
+        }
+    }
+
+    let mut game = games.get_game(game_id).await?;
+
+    game.abandon(reason)?;
+    games.update_game(&mut game, request_id).await?;
+
+    Ok(game)
+}
+
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id, request_id = parameters.request_id))]
+pub async fn cancel_last_score<G>(
+    parameters: CancelLastScoreParameters<'_, G>,
+) -> Result<Requested, Error>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
+{
+    let CancelLastScoreParameters {
+        game_id,
+        games,
+        request_id,
+    } = parameters;
+
+    if let Some(request_id) = request_id {
+        if let Some(game) = games.get_requested_game(request_id, Some(game_id)).await? {
+            tracing::info!("Request already applied");
+
+            return Ok(Requested::Replayed(game));
+        }
+    }
+
+    let mut game = games.get_game(game_id).await?;
+
+    game.cancel_last_score()?;
+    games.update_game(&mut game, request_id).await?;
+
+    Ok(Requested::Applied(game))
+}
+
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id, round_number = %parameters.round_number, player_number = %parameters.player_number, points = %parameters.score.points(), request_id = parameters.request_id))]
+pub async fn correct_score<G>(parameters: CorrectScoreParameters<'_, G>) -> Result<Requested, Error>
+where
+    G: CorrectGame + GetGame + GetRequestedGame,
+{
+    let CorrectScoreParameters {
+        game_id,
+        games,
+        player_number,
+        request_id,
+        round_number,
+        score,
+    } = parameters;
+
+    if let Some(request_id) = request_id {
+        if let Some(game) = games.get_requested_game(request_id, Some(game_id)).await? {
+            tracing::info!("Request already applied");
+
+            return Ok(Requested::Replayed(game));
+        }
+    }
+
+    let mut game = games.get_game(game_id).await?;
+    let was_finished = game.winner().is_some();
+
+    let correction = game.correct_score(round_number, player_number, score)?;
+    games
+        .correct_game(&mut game, &correction, request_id)
+        .await?;
+
+    Ok(Requested::applied(game, was_finished))
+}
+
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id, points = %parameters.score.points(), request_id = parameters.request_id))]
+pub async fn count_score<G>(parameters: CountScoreParameters<'_, G>) -> Result<Requested, Error>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
 {
     let CountScoreParameters {
+        expected_turn,
         game_id,
         score,
         games,
+        request_id,
     } = parameters;
 
+    if let Some(request_id) = request_id {
+        if let Some(game) = games.get_requested_game(request_id, Some(game_id)).await? {
+            tracing::info!("Request already applied");
+
+            return Ok(Requested::Replayed(game));
+        }
+    }
+
     let mut game = games.get_game(game_id).await?;
 
+    game.check_turn(&expected_turn)?;
     game.count_score(score)?;
-    games.update_game(&mut game).await?;
+    games.update_game(&mut game, request_id).await?;
 
-    Ok(game)
+    Ok(Requested::applied(game, false))
 }
 
-pub async fn initialize_game<G>(parameters: InitializeGameParameters<'_, G>) -> Result<Game, Error>
+#[tracing::instrument(skip_all, fields(players_number = %parameters.players_number, points_limit = %parameters.points_limit, request_id = parameters.request_id))]
+pub async fn initialize_game<G>(
+    parameters: InitializeGameParameters<'_, G>,
+) -> Result<Requested, Error>
 where
-    G: InsertGame,
+    G: GetRequestedGame + InsertGame,
 {
     let InitializeGameParameters {
         games,
         players_number,
         points_limit,
+        request_id,
+        shot_clock,
     } = parameters;
 
+    if let Some(request_id) = request_id {
+        if let Some(game) = games.get_requested_game(request_id, None).await? {
+            tracing::info!("Request already applied");
+
+            return Ok(Requested::Replayed(game));
+        }
+    }
+
     let mut game = Game::new(NewGameParameters {
         players_number,
         points_limit,
+        shot_clock,
     })?;
 
-    games.insert_game(&mut game).await?;
+    games.insert_game(&mut game, request_id).await?;
 
-    Ok(game)
+    Ok(Requested::Applied(game))
+}
+
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id, request_id = parameters.request_id))]
+pub async fn delete_game<G>(parameters: DeleteGameParameters<'_, G>) -> Result<(), Error>
+where
+    G: DeleteGame + GetRequestedGame,
+{
+    let DeleteGameParameters {
+        games,
+        game_id,
+        request_id,
+    } = parameters;
+
+    if let Some(request_id) = request_id {
+        if games
+            .get_requested_game(request_id, Some(game_id))
+            .await?
+            .is_some()
+        {
+            tracing::info!("Request already applied");
+
+            return Ok(());
+        }
+    }
+
+    games.delete_game(game_id, request_id).await
 }
 
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id))]
 pub async fn get_game<G>(parameters: GetGameParameters<'_, G>) -> Result<Game, Error>
 where
     G: GetGame,
@@ -103,11 +459,143 @@ where
     Ok(game)
 }
 
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id))]
+pub async fn get_game_timeline<G>(
+    parameters: GetGameTimelineParameters<'_, G>,
+) -> Result<Vec<TimelineStep>, Error>
+where
+    G: GetGame,
+{
+    let GetGameTimelineParameters { games, game_id } = parameters;
+
+    let game = games.get_game(game_id).await?;
+
+    game.timeline()
+}
+
+#[tracing::instrument(skip_all)]
 pub async fn list_games<G>(parameters: ListGamesParameters<'_, G>) -> Result<Vec<Game>, Error>
 where
     G: ListGames,
 {
-    let games = parameters.games.list_games().await?;
+    let games = parameters
+        .games
+        .list_games(parameters.include_deleted)
+        .await?;
 
     Ok(games)
 }
+
+#[tracing::instrument(skip_all)]
+pub async fn list_game_summaries<G>(
+    parameters: ListGameSummariesParameters<'_, G>,
+) -> Result<Vec<GameSummary>, Error>
+where
+    G: ListGameSummaries,
+{
+    let summaries = parameters
+        .games
+        .list_game_summaries(parameters.include_deleted)
+        .await?;
+
+    Ok(summaries)
+}
+
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id))]
+pub async fn list_score_corrections<G>(
+    parameters: ListScoreCorrectionsParameters<'_, G>,
+) -> Result<Vec<ScoreCorrection>, Error>
+where
+    G: GetGame + ListScoreCorrections,
+{
+    let ListScoreCorrectionsParameters { game_id, games } = parameters;
+
+    // Fails for unknown and deleted games, which have no corrections to show.
+    games.get_game(game_id).await?;
+
+    let corrections = games.list_score_corrections(game_id).await?;
+
+    Ok(corrections)
+}
+
+/// Counts all scores or none, for clients that scored offline and sync later.
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id, visits = parameters.scores.len(), request_id = parameters.request_id))]
+pub async fn submit_visits<G>(
+    parameters: SubmitVisitsParameters<'_, G>,
+) -> Result<Requested, SubmitVisitsError>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
+{
+    let SubmitVisitsParameters {
+        expected_turn,
+        game_id,
+        games,
+        request_id,
+        scores,
+    } = parameters;
+
+    if let Some(request_id) = request_id {
+        if let Some(game) = games.get_requested_game(request_id, Some(game_id)).await? {
+            tracing::info!("Request already applied");
+
+            return Ok(Requested::Replayed(game));
+        }
+    }
+
+    let mut game = games.get_game(game_id).await?;
+
+    game.check_turn(&expected_turn)?;
+
+    for (index, score) in scores.into_iter().enumerate() {
+        game.count_offline_score(score)
+            .map_err(|error| SubmitVisitsError::Visit { error, index })?;
+    }
+
+    games.update_game(&mut game, request_id).await?;
+
+    Ok(Requested::applied(game, false))
+}
+
+/// Changes settings of a game nobody has scored in yet.
+#[tracing::instrument(skip_all, fields(game_id = %parameters.game_id, request_id = parameters.request_id))]
+pub async fn update_game_settings<G>(
+    parameters: UpdateGameSettingsParameters<'_, G>,
+) -> Result<Game, Error>
+where
+    G: GetGame + GetRequestedGame + UpdateGame,
+{
+    let UpdateGameSettingsParameters {
+        game_id,
+        games,
+        players_number,
+        points_limit,
+        request_id,
+        shot_clock,
+    } = parameters;
+
+    if let Some(request_id) = request_id {
+        if let Some(game) = games.get_requested_game(request_id, Some(game_id)).await? {
+            tracing::info!("Request already applied");
+
+            return Ok(game);
+        }
+    }
+
+    let mut game = games.get_game(game_id).await?;
+
+    if let Some(players_number) = players_number {
+        game.change_players_number(players_number)?;
+    }
+
+    if let Some(points_limit) = points_limit {
+        game.change_points_limit(points_limit)?;
+    }
+
+    if let Some(shot_clock) = shot_clock {
+        game.change_shot_clock(shot_clock)?;
+    }
+
+    games.update_game(&mut game, request_id).await?;
+
+    Ok(game)
+}
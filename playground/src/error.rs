@@ -1,21 +1,54 @@
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    #[error("Already exists: {0}")]
-    AlreadyExists(String),
+    #[error("Aborted: {1}")]
+    Aborted(Reason, String),
 
-    #[error("Invalid argument: {0}")]
-    InvalidArgument(String),
+    #[error("Already exists: {1}")]
+    AlreadyExists(Reason, String),
 
-    #[error("Failed precondition: {0}")]
-    FailedPrecondition(String),
+    #[error("Invalid argument: {1}")]
+    InvalidArgument(Reason, String),
 
-    #[error("Not found: {0}")]
-    NotFound(String),
+    #[error("Failed precondition: {1}")]
+    FailedPrecondition(Reason, String),
+
+    #[error("Not found: {1}")]
+    NotFound(Reason, String),
 
     #[error("Unexpected: {0}")]
     Unexpected(#[from] eyre::Report),
 }
 
+/// Machine readable cause of an error, for clients to tell errors apart without
+/// parsing descriptions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reason {
+    ApiTokenNotFound,
+    DuplicateRequestId,
+    EndTimeBeforeStartTime,
+    GameAbandoned,
+    GameChanged,
+    GameFinished,
+    GameInProgress,
+    GameNotFound,
+    GameNotStarted,
+    InvalidGame,
+    InvalidPoints,
+    InvalidTurnTimeLimit,
+    InvalidUuid,
+    NegativeNumber,
+    PlayersNumberOverLimit,
+    RoundNotFound,
+    ScoreOverLimit,
+    TimeAlreadyAssigned,
+    UnexpectedTurn,
+    UnknownField,
+    UnknownRole,
+    UpdateTimeBeforePrevious,
+    ZeroNumber,
+    ZeroPointsLimit,
+}
+
 impl<T> From<Error> for Result<T, Error> {
     fn from(value: Error) -> Self {
         Err(value)
@@ -23,15 +56,58 @@ impl<T> From<Error> for Result<T, Error> {
 }
 
 impl Error {
-    pub(crate) fn failed_precondition(description: impl ToString) -> Self {
-        Self::FailedPrecondition(description.to_string())
+    pub(crate) fn failed_precondition(reason: Reason, description: impl ToString) -> Self {
+        Self::FailedPrecondition(reason, description.to_string())
+    }
+
+    pub(crate) fn invalid_argument(reason: Reason, description: impl ToString) -> Self {
+        Self::InvalidArgument(reason, description.to_string())
     }
 
-    pub(crate) fn invalid_argument(description: impl ToString) -> Self {
-        Self::InvalidArgument(description.to_string())
+    /// Reason of client errors, `None` for unexpected ones.
+    pub fn reason(&self) -> Option<Reason> {
+        match self {
+            Error::Aborted(reason, _)
+            | Error::AlreadyExists(reason, _)
+            | Error::InvalidArgument(reason, _)
+            | Error::FailedPrecondition(reason, _)
+            | Error::NotFound(reason, _) => Some(*reason),
+            Error::Unexpected(_) => None,
+        }
     }
 
     pub(crate) fn unexpected(description: impl ToString) -> Self {
         Self::Unexpected(eyre::Report::msg(description.to_string()))
     }
 }
+
+impl Reason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Reason::ApiTokenNotFound => "API_TOKEN_NOT_FOUND",
+            Reason::DuplicateRequestId => "DUPLICATE_REQUEST_ID",
+            Reason::EndTimeBeforeStartTime => "END_TIME_BEFORE_START_TIME",
+            Reason::GameAbandoned => "GAME_ABANDONED",
+            Reason::GameChanged => "GAME_CHANGED",
+            Reason::GameFinished => "GAME_FINISHED",
+            Reason::GameInProgress => "GAME_IN_PROGRESS",
+            Reason::GameNotFound => "GAME_NOT_FOUND",
+            Reason::GameNotStarted => "GAME_NOT_STARTED",
+            Reason::InvalidGame => "INVALID_GAME",
+            Reason::InvalidPoints => "INVALID_POINTS",
+            Reason::InvalidTurnTimeLimit => "INVALID_TURN_TIME_LIMIT",
+            Reason::InvalidUuid => "INVALID_UUID",
+            Reason::NegativeNumber => "NEGATIVE_NUMBER",
+            Reason::PlayersNumberOverLimit => "PLAYERS_NUMBER_OVER_LIMIT",
+            Reason::RoundNotFound => "ROUND_NOT_FOUND",
+            Reason::ScoreOverLimit => "SCORE_OVER_LIMIT",
+            Reason::TimeAlreadyAssigned => "TIME_ALREADY_ASSIGNED",
+            Reason::UnexpectedTurn => "UNEXPECTED_TURN",
+            Reason::UnknownField => "UNKNOWN_FIELD",
+            Reason::UnknownRole => "UNKNOWN_ROLE",
+            Reason::UpdateTimeBeforePrevious => "UPDATE_TIME_BEFORE_PREVIOUS",
+            Reason::ZeroNumber => "ZERO_NUMBER",
+            Reason::ZeroPointsLimit => "ZERO_POINTS_LIMIT",
+        }
+    }
+}
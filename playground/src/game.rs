@@ -1,9 +1,16 @@
-use crate::{Error, Number, PlayerScore, Points, Score};
-use chrono::{DateTime, Utc};
+use crate::{
+    Error, LateVisitPolicy, NewScoreCorrectionParameters, Number, PlayerScore, Points, Reason,
+    Score, ScoreCorrection, ShotClock,
+};
+use chrono::{DateTime, SubsecRound, Utc};
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use uuid::Uuid;
 
+/// Most players a game can have, every player is listed with every read of the game.
+const PLAYERS_NUMBER_LIMIT: usize = 16;
+
+#[derive(Clone)]
 pub struct Game {
     create_time: Option<DateTime<Utc>>,
     end_time: Option<DateTime<Utc>>,
@@ -11,30 +18,41 @@ pub struct Game {
     players_number: Number,
     points_limit: Points,
     rounds: BTreeSet<Round>,
+    shot_clock: Option<ShotClock>,
     start_time: Option<DateTime<Utc>>,
     state: State,
     update_time: Option<DateTime<Utc>>,
 }
 
+#[derive(Clone)]
 pub struct Round {
+    /// Counted after the shot clock of its turn ran out.
+    late: bool,
     number: Number,
     player_number: Number,
     player_score: PlayerScore,
+    /// When the visit was counted, unknown for visits counted before times were kept.
+    time: Option<DateTime<Utc>>,
 }
 
 pub struct NewRoundParameters {
+    pub late: bool,
     pub number: Number,
     pub player_number: Number,
     pub player_score: PlayerScore,
+    pub time: Option<DateTime<Utc>>,
 }
 
 pub struct LoadGameParameters {
+    pub abandon_reason: Option<String>,
+    pub abandon_time: Option<DateTime<Utc>>,
     pub create_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
     pub id: Uuid,
     pub players_number: Number,
     pub points_limit: Points,
     pub rounds: Vec<Round>,
+    pub shot_clock: Option<ShotClock>,
     pub start_time: Option<DateTime<Utc>>,
     pub update_time: DateTime<Utc>,
 }
@@ -42,9 +60,43 @@ pub struct LoadGameParameters {
 pub struct NewGameParameters {
     pub players_number: Number,
     pub points_limit: Points,
+    pub shot_clock: Option<ShotClock>,
+}
+
+/// Turn a client is about to score, as its copy of the game shows.
+/// Unset numbers are not checked.
+#[derive(Default)]
+pub struct ExpectedTurn {
+    pub player_number: Option<Number>,
+    pub round_number: Option<Number>,
 }
 
 impl Game {
+    /// Stops an unfinished game for good, its rounds are kept as they are.
+    pub fn abandon(&mut self, reason: impl ToString) -> Result<(), Error> {
+        match self.state {
+            State::Abandoned(_) => {
+                return Error::failed_precondition(
+                    Reason::GameAbandoned,
+                    "Game is already abandoned",
+                )
+                .into()
+            }
+            State::Finished(_) => {
+                return Error::failed_precondition(
+                    Reason::GameFinished,
+                    "Cannot abandon a game that is over",
+                )
+                .into()
+            }
+            State::NotStarted(_) | State::InProgress(_) => {}
+        }
+
+        self.state = State::abandoned(reason.to_string(), Utc::now());
+
+        Ok(())
+    }
+
     pub fn assign_create_time(&mut self, create_time: DateTime<Utc>) -> Result<(), Error> {
         if self.create_time.is_some() {
             return Error::unexpected("Attempt to reassign game creation time").into();
@@ -65,16 +117,20 @@ impl Game {
         Ok(())
     }
 
-    fn assign_rounds(&mut self, rounds: Vec<Round>) -> Result<(), Error> {
+    fn assign_rounds(
+        &mut self,
+        rounds: Vec<Round>,
+        start_time: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
         if !self.rounds.is_empty() {
             return Error::unexpected("Game rounds reassignment attempt").into();
         }
 
-        for round in rounds {
+        for (index, round) in rounds.iter().enumerate() {
             let points = round.player_score().score().points();
             let score = Score::new(points.value())?;
 
-            self.count_score(score)?;
+            self.count_visit(score, visit_time(round, index, start_time), round.late)?;
         }
 
         Ok(())
@@ -82,7 +138,11 @@ impl Game {
 
     fn assign_start_time(&mut self, start_time: DateTime<Utc>) -> Result<(), Error> {
         if self.start_time.is_some() {
-            return Error::failed_precondition("Attempt to reassign start time").into();
+            return Error::failed_precondition(
+                Reason::TimeAlreadyAssigned,
+                "Attempt to reassign start time",
+            )
+            .into();
         }
 
         self.start_time = Some(start_time);
@@ -92,16 +152,27 @@ impl Game {
 
     fn assign_end_time(&mut self, end_time: DateTime<Utc>) -> Result<(), Error> {
         if self.end_time.is_some() {
-            return Error::failed_precondition("Attempt to reassign end time").into();
+            return Error::failed_precondition(
+                Reason::TimeAlreadyAssigned,
+                "Attempt to reassign end time",
+            )
+            .into();
         }
 
         if let Some(time) = self.start_time {
             if time > end_time {
-                return Error::invalid_argument("End time cannot be less than start time").into();
+                return Error::invalid_argument(
+                    Reason::EndTimeBeforeStartTime,
+                    "End time cannot be less than start time",
+                )
+                .into();
             }
         } else {
-            return Error::failed_precondition("Attempt to assign end time without start time")
-                .into();
+            return Error::failed_precondition(
+                Reason::GameNotStarted,
+                "Attempt to assign end time without start time",
+            )
+            .into();
         }
 
         self.end_time = Some(end_time);
@@ -109,17 +180,38 @@ impl Game {
         Ok(())
     }
 
-    fn change_players_number(&mut self, players_number: Number) -> Result<(), Error> {
+    pub fn change_players_number(&mut self, players_number: Number) -> Result<(), Error> {
+        if players_number.value() > PLAYERS_NUMBER_LIMIT {
+            let description = format!(
+                "The maximum number of players allowed is {}. Given: {}",
+                PLAYERS_NUMBER_LIMIT, players_number
+            );
+
+            return Error::invalid_argument(Reason::PlayersNumberOverLimit, description).into();
+        };
+
+        if self.is_abandoned() {
+            return Error::failed_precondition(
+                Reason::GameAbandoned,
+                "Cannot update players number when game is abandoned",
+            )
+            .into();
+        };
+
         if self.is_in_progress() {
             return Error::failed_precondition(
+                Reason::GameInProgress,
                 "Cannot update players number when game is in progress",
             )
             .into();
         };
 
         if self.is_finished() {
-            return Error::failed_precondition("Cannot update players number when game is over")
-                .into();
+            return Error::failed_precondition(
+                Reason::GameFinished,
+                "Cannot update players number when game is over",
+            )
+            .into();
         };
 
         self.players_number = players_number;
@@ -127,24 +219,70 @@ impl Game {
         Ok(())
     }
 
-    fn change_points_limit(&mut self, points_limit: Points) -> Result<(), Error> {
+    /// Players of a game that has not started yet need all of the new limit to win.
+    pub fn change_points_limit(&mut self, points_limit: Points) -> Result<(), Error> {
         if points_limit.is_zero() {
-            return Error::invalid_argument("Points limit cannot be zero").into();
+            return Error::invalid_argument(Reason::ZeroPointsLimit, "Points limit cannot be zero")
+                .into();
+        };
+
+        if self.is_abandoned() {
+            return Error::failed_precondition(
+                Reason::GameAbandoned,
+                "Cannot update points limit when game is abandoned",
+            )
+            .into();
         };
 
         if self.is_in_progress() {
             return Error::failed_precondition(
+                Reason::GameInProgress,
                 "Cannot update points limit when game is in progress",
             )
             .into();
         };
 
         if self.is_finished() {
-            return Error::failed_precondition("Cannot update points limit when Game is over")
-                .into();
+            return Error::failed_precondition(
+                Reason::GameFinished,
+                "Cannot update points limit when Game is over",
+            )
+            .into();
         };
 
         self.points_limit = points_limit;
+        self.state = State::not_started(points_limit);
+
+        Ok(())
+    }
+
+    /// `None` turns the shot clock off.
+    pub fn change_shot_clock(&mut self, shot_clock: Option<ShotClock>) -> Result<(), Error> {
+        if self.is_abandoned() {
+            return Error::failed_precondition(
+                Reason::GameAbandoned,
+                "Cannot update shot clock when game is abandoned",
+            )
+            .into();
+        };
+
+        if self.is_in_progress() {
+            return Error::failed_precondition(
+                Reason::GameInProgress,
+                "Cannot update shot clock when game is in progress",
+            )
+            .into();
+        };
+
+        if self.is_finished() {
+            return Error::failed_precondition(
+                Reason::GameFinished,
+                "Cannot update shot clock when game is over",
+            )
+            .into();
+        };
+
+        self.shot_clock = shot_clock;
 
         Ok(())
     }
@@ -152,7 +290,11 @@ impl Game {
     pub fn change_update_time(&mut self, update_time: DateTime<Utc>) -> Result<(), Error> {
         if let Some(time) = self.update_time {
             if time > update_time {
-                return Error::invalid_argument("Update time cannot be less than before").into();
+                return Error::invalid_argument(
+                    Reason::UpdateTimeBeforePrevious,
+                    "Update time cannot be less than before",
+                )
+                .into();
             }
         }
 
@@ -161,7 +303,101 @@ impl Game {
         Ok(())
     }
 
+    /// Removes the last counted visit, the game goes back to the turn of that visit.
+    pub fn cancel_last_score(&mut self) -> Result<(), Error> {
+        if self.is_abandoned() {
+            return Error::failed_precondition(
+                Reason::GameAbandoned,
+                "Cannot cancel a score when game is abandoned",
+            )
+            .into();
+        }
+
+        if self.rounds.is_empty() {
+            return Error::failed_precondition(
+                Reason::GameNotStarted,
+                "Cannot cancel a score when no score is counted",
+            )
+            .into();
+        }
+
+        let mut replayed = Game::new(NewGameParameters {
+            players_number: self.players_number,
+            points_limit: self.points_limit,
+            shot_clock: self.shot_clock,
+        })?;
+
+        for (index, round) in self.rounds.iter().enumerate().take(self.rounds.len() - 1) {
+            replayed.count_visit(
+                round.player_score.score().clone(),
+                visit_time(round, index, self.start_time),
+                round.late,
+            )?;
+        }
+
+        self.rounds = replayed.rounds;
+        self.state = replayed.state;
+        // Only the last visit can win a game, and the first one starts it.
+        self.end_time = None;
+        self.start_time = self.start_time.filter(|_| !self.rounds.is_empty());
+
+        Ok(())
+    }
+
+    /// Fails when the game has moved on from the turn the client expects,
+    /// because someone else counted a score meanwhile.
+    pub fn check_turn(&self, expected: &ExpectedTurn) -> Result<(), Error> {
+        let (player_number, round_number) = match &self.state {
+            State::NotStarted(state) => (state.player_number(), Number::one()),
+            State::InProgress(state) => (state.player_number(), state.round_number()),
+            // Counting fails with a more specific reason.
+            State::Abandoned(_) | State::Finished(_) => return Ok(()),
+        };
+
+        let player_differs = expected
+            .player_number
+            .is_some_and(|number| number != player_number);
+        let round_differs = expected
+            .round_number
+            .is_some_and(|number| number != round_number);
+
+        if player_differs || round_differs {
+            return Error::failed_precondition(
+                Reason::UnexpectedTurn,
+                format!("It is Player{player_number}'s turn in round {round_number}"),
+            )
+            .into();
+        }
+
+        Ok(())
+    }
+
+    /// A visit over the shot clock is marked late, and scores zero if the clock says so.
     pub fn count_score(&mut self, score: Score) -> Result<(), Error> {
+        let time = visit_time_now();
+        let late = self.is_late(time);
+
+        let score = match self.shot_clock.map(|clock| clock.late_visit_policy()) {
+            Some(LateVisitPolicy::ScoreZero) if late => Score::new(0)?,
+            _ => score,
+        };
+
+        self.count_visit(score, Some(time), late)
+    }
+
+    /// Counts a score of a visit made offline. It is counted when the client syncs, long
+    /// after its turn may have run out, so it is never on the shot clock.
+    pub fn count_offline_score(&mut self, score: Score) -> Result<(), Error> {
+        self.count_visit(score, Some(visit_time_now()), false)
+    }
+
+    /// Counts a score of a visit made at `time`, when it is known.
+    pub(crate) fn count_visit(
+        &mut self,
+        score: Score,
+        time: Option<DateTime<Utc>>,
+        late: bool,
+    ) -> Result<(), Error> {
         match &self.state {
             State::NotStarted(state) => {
                 let points = score.points();
@@ -179,6 +415,8 @@ impl Game {
                     number: Number::one(),
                     player_number: state.player_number(),
                     player_score,
+                    time,
+                    late,
                 });
 
                 if !insert_result {
@@ -193,7 +431,7 @@ impl Game {
                         winner: player_number,
                     });
 
-                    self.assign_end_time(Utc::now())?;
+                    self.assign_end_time(time.unwrap_or_else(Utc::now))?;
 
                     return Ok(());
                 }
@@ -205,7 +443,7 @@ impl Game {
                     player_number = unsafe { Number::new_unchecked(2) };
                 }
 
-                self.assign_start_time(Utc::now())?;
+                self.assign_start_time(time.unwrap_or_else(Utc::now))?;
 
                 self.state = State::InProgress(InProgressState {
                     player_number,
@@ -215,9 +453,16 @@ impl Game {
 
                 Ok(())
             }
-            State::Finished(_) => {
-                Error::failed_precondition("Cannot count a score when game is over").into()
-            }
+            State::Abandoned(_) => Error::failed_precondition(
+                Reason::GameAbandoned,
+                "Cannot count a score when game is abandoned",
+            )
+            .into(),
+            State::Finished(_) => Error::failed_precondition(
+                Reason::GameFinished,
+                "Cannot count a score when game is over",
+            )
+            .into(),
             State::InProgress(state) => {
                 let points = score.points();
 
@@ -239,6 +484,8 @@ impl Game {
                     number: state.round_number,
                     player_number: state.player_number,
                     player_score,
+                    time,
+                    late,
                 });
 
                 if !insert_result {
@@ -253,7 +500,7 @@ impl Game {
                         winner: state.player_number,
                     });
 
-                    self.assign_end_time(Utc::now())?;
+                    self.assign_end_time(time.unwrap_or_else(Utc::now))?;
 
                     return Ok(());
                 }
@@ -288,6 +535,80 @@ impl Game {
         }
     }
 
+    /// Replaces the score of a counted visit and counts the visits after it again, so
+    /// overthrows, the current turn and the winner follow the corrected score.
+    pub fn correct_score(
+        &mut self,
+        round_number: Number,
+        player_number: Number,
+        score: Score,
+    ) -> Result<ScoreCorrection, Error> {
+        if self.is_abandoned() {
+            return Error::failed_precondition(
+                Reason::GameAbandoned,
+                "Cannot correct a score when game is abandoned",
+            )
+            .into();
+        }
+
+        let Some(previous_score) = self
+            .rounds
+            .iter()
+            .find(|round| round.number == round_number && round.player_number == player_number)
+            .map(|round| round.player_score.score().clone())
+        else {
+            return Err(Error::NotFound(
+                Reason::RoundNotFound,
+                format!("Round {round_number} of Player{player_number}"),
+            ));
+        };
+
+        let mut replayed = Game::new(NewGameParameters {
+            players_number: self.players_number,
+            points_limit: self.points_limit,
+            shot_clock: self.shot_clock,
+        })?;
+
+        for (index, round) in self.rounds.iter().enumerate() {
+            let counted = if round.number == round_number && round.player_number == player_number {
+                score.clone()
+            } else {
+                round.player_score.score().clone()
+            };
+
+            if replayed.is_finished() {
+                return Error::failed_precondition(
+                    Reason::GameFinished,
+                    format!(
+                        "Corrected score finishes the game before round {} of Player{}",
+                        round.number, round.player_number
+                    ),
+                )
+                .into();
+            }
+
+            replayed.count_visit(
+                counted,
+                visit_time(round, index, self.start_time),
+                round.late,
+            )?;
+        }
+
+        self.rounds = replayed.rounds;
+        self.state = replayed.state;
+        // The first visit stays, and so does the start time. A game still won keeps
+        // its end time, a game won by the correction ends with its winning visit.
+        self.end_time = replayed.end_time.map(|time| self.end_time.unwrap_or(time));
+
+        Ok(ScoreCorrection::new(NewScoreCorrectionParameters {
+            player_number,
+            previous_score,
+            round_number,
+            score,
+            time: Utc::now(),
+        }))
+    }
+
     pub fn create_time(&self) -> Option<DateTime<Utc>> {
         self.create_time
     }
@@ -308,12 +629,17 @@ impl Game {
             players_number: Number::one(),
             points_limit: Points::zero(),
             rounds: BTreeSet::new(),
+            shot_clock: None,
             start_time: None,
             state: State::not_started(Points::zero()),
             update_time: None,
         }
     }
 
+    fn is_abandoned(&self) -> bool {
+        matches!(self.state, State::Abandoned(_))
+    }
+
     fn is_finished(&self) -> bool {
         matches!(self.state, State::Finished(_))
     }
@@ -324,26 +650,35 @@ impl Game {
 
     pub fn load(parameters: LoadGameParameters) -> Result<Self, Error> {
         let LoadGameParameters {
+            abandon_reason,
+            abandon_time,
             create_time,
-            end_time: _,
+            end_time,
             id,
             players_number,
             points_limit,
             rounds,
-            start_time: _,
+            shot_clock,
+            start_time,
             update_time,
         } = parameters;
 
         let mut game = Self::new(NewGameParameters {
             points_limit,
             players_number,
+            shot_clock,
         })?;
 
         game.assign_id(id)?;
         game.assign_create_time(create_time)?;
-        game.assign_rounds(rounds)?;
+        game.assign_rounds(rounds, start_time)?;
+        game.restore_times(start_time, end_time);
         game.change_update_time(update_time)?;
 
+        if let Some(time) = abandon_time {
+            game.state = State::abandoned(abandon_reason.unwrap_or_default(), time);
+        }
+
         Ok(game)
     }
 
@@ -351,12 +686,14 @@ impl Game {
         let NewGameParameters {
             points_limit,
             players_number,
+            shot_clock,
         } = parameters;
 
         let mut game = Self::init();
 
         game.change_points_limit(points_limit)?;
         game.change_players_number(players_number)?;
+        game.change_shot_clock(shot_clock)?;
 
         Ok(game)
     }
@@ -365,10 +702,30 @@ impl Game {
         self.players_number
     }
 
+    /// Replaying rounds stamps start and end times with the current time,
+    /// so stored times take precedence wherever the replay agrees with them.
+    fn restore_times(
+        &mut self,
+        start_time: Option<DateTime<Utc>>,
+        end_time: Option<DateTime<Utc>>,
+    ) {
+        if let (Some(_), Some(time)) = (self.start_time, start_time) {
+            self.start_time = Some(time);
+        }
+
+        if let (Some(_), Some(time)) = (self.end_time, end_time) {
+            self.end_time = Some(time);
+        }
+    }
+
     pub fn rounds(&self) -> &BTreeSet<Round> {
         &self.rounds
     }
 
+    pub fn shot_clock(&self) -> Option<&ShotClock> {
+        self.shot_clock.as_ref()
+    }
+
     pub fn start_time(&self) -> Option<DateTime<Utc>> {
         self.start_time
     }
@@ -420,18 +777,26 @@ impl Ord for Round {
 impl Round {
     pub fn new(parameters: NewRoundParameters) -> Self {
         let NewRoundParameters {
+            late,
             number,
             player_number,
             player_score,
+            time,
         } = parameters;
 
         Self {
+            late,
             number,
             player_number,
             player_score,
+            time,
         }
     }
 
+    pub fn is_late(&self) -> bool {
+        self.late
+    }
+
     pub fn number(&self) -> Number {
         self.number
     }
@@ -443,34 +808,79 @@ impl Round {
     pub fn player_score(&self) -> &PlayerScore {
         &self.player_score
     }
+
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        self.time
+    }
 }
 
+#[derive(Clone)]
 pub enum State {
     NotStarted(NotStartedState),
     InProgress(InProgressState),
     Finished(FinishedState),
+    Abandoned(AbandonedState),
 }
 
 impl State {
+    pub(crate) fn abandoned(reason: String, time: DateTime<Utc>) -> Self {
+        Self::Abandoned(AbandonedState { reason, time })
+    }
+
+    pub(crate) fn finished(winner: Number) -> Self {
+        Self::Finished(FinishedState { winner })
+    }
+
+    pub(crate) fn in_progress(
+        player_number: Number,
+        round_number: Number,
+        points_to_win: Points,
+    ) -> Self {
+        Self::InProgress(InProgressState {
+            player_number,
+            round_number,
+            points_to_win,
+        })
+    }
+
     pub(crate) fn not_started(points_to_win: Points) -> Self {
         Self::NotStarted(NotStartedState { points_to_win })
     }
 }
 
+#[derive(Clone)]
 pub struct InProgressState {
     player_number: Number,
     round_number: Number,
     points_to_win: Points,
 }
 
+#[derive(Clone)]
 pub struct NotStartedState {
     points_to_win: Points,
 }
 
+#[derive(Clone)]
 pub struct FinishedState {
     winner: Number,
 }
 
+#[derive(Clone)]
+pub struct AbandonedState {
+    reason: String,
+    time: DateTime<Utc>,
+}
+
+impl AbandonedState {
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
+
 impl FinishedState {
     pub fn winner(&self) -> Number {
         self.winner
@@ -485,6 +895,10 @@ impl InProgressState {
     pub fn points_to_win(&self) -> Points {
         self.points_to_win
     }
+
+    pub fn round_number(&self) -> Number {
+        self.round_number
+    }
 }
 
 impl NotStartedState {
@@ -495,4 +909,335 @@ impl NotStartedState {
     pub fn points_to_win(&self) -> Points {
         self.points_to_win
     }
+
+    pub fn round_number(&self) -> Number {
+        Number::one()
+    }
+}
+
+/// Microseconds, so a saved game reports the same times as the counted one.
+fn visit_time_now() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(6)
+}
+
+/// Time to replay a visit with. Visits counted before times were kept have none, the
+/// first of them is taken to have started the game when it was started.
+pub(crate) fn visit_time(
+    round: &Round,
+    index: usize,
+    start_time: Option<DateTime<Utc>>,
+) -> Option<DateTime<Utc>> {
+    round.time.or(start_time.filter(|_| index == 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_game(players_number: usize, points_limit: u16) -> Game {
+        Game::new(NewGameParameters {
+            players_number: Number::new(players_number).unwrap(),
+            points_limit: Points::new(points_limit),
+            shot_clock: None,
+        })
+        .unwrap()
+    }
+
+    fn count_scores(game: &mut Game, scores: &[u16]) {
+        for &points in scores {
+            game.count_score(Score::new(points).unwrap()).unwrap();
+        }
+    }
+
+    fn expected_turn(player_number: usize, round_number: usize) -> ExpectedTurn {
+        ExpectedTurn {
+            player_number: Some(number(player_number)),
+            round_number: Some(number(round_number)),
+        }
+    }
+
+    fn number(value: usize) -> Number {
+        Number::new(value).unwrap()
+    }
+
+    fn points_to_win(game: &Game) -> u16 {
+        match game.state() {
+            State::InProgress(state) => state.points_to_win().value(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_change_points_limit() {
+        let mut game = new_game(2, 301);
+
+        game.change_points_limit(Points::new(501)).unwrap();
+
+        assert_eq!(game.points_limit().value(), 501);
+
+        match game.state() {
+            State::NotStarted(state) => assert_eq!(state.points_to_win().value(), 501),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_check_turn() {
+        let mut game = new_game(2, 301);
+
+        assert!(game.check_turn(&expected_turn(1, 1)).is_ok());
+
+        count_scores(&mut game, &[60, 45]);
+
+        assert!(game.check_turn(&expected_turn(1, 2)).is_ok());
+        assert!(game.check_turn(&ExpectedTurn::default()).is_ok());
+    }
+
+    #[test]
+    fn test_check_turn_moved_on() {
+        let mut game = new_game(2, 301);
+
+        count_scores(&mut game, &[60]);
+
+        let result = game.check_turn(&expected_turn(1, 1));
+
+        match result {
+            Err(Error::FailedPrecondition(reason, msg)) => {
+                assert_eq!(reason, Reason::UnexpectedTurn);
+                assert_eq!(msg, "It is Player2's turn in round 1");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_check_turn_when_game_is_over() {
+        let mut game = new_game(1, 101);
+
+        count_scores(&mut game, &[60, 41]);
+
+        assert!(game.check_turn(&expected_turn(1, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_abandon() {
+        let mut game = new_game(2, 301);
+
+        count_scores(&mut game, &[60]);
+
+        assert!(game.abandon("Players left").is_ok());
+
+        match game.state() {
+            State::Abandoned(state) => assert_eq!(state.reason(), "Players left"),
+            _ => unreachable!(),
+        }
+
+        assert_eq!(game.rounds().len(), 1);
+    }
+
+    #[test]
+    fn test_abandon_twice() {
+        let mut game = new_game(2, 301);
+
+        game.abandon("Players left").unwrap();
+
+        let result = game.abandon("Players left again");
+
+        match result {
+            Err(Error::FailedPrecondition(reason, msg)) => {
+                assert_eq!(reason, Reason::GameAbandoned);
+                assert_eq!(msg, "Game is already abandoned");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_abandon_finished_game() {
+        let mut game = new_game(1, 101);
+
+        count_scores(&mut game, &[60, 41]);
+
+        let result = game.abandon("Players left");
+
+        match result {
+            Err(Error::FailedPrecondition(reason, _)) => {
+                assert_eq!(reason, Reason::GameFinished)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_count_score_when_game_is_abandoned() {
+        let mut game = new_game(2, 301);
+
+        game.abandon("Players left").unwrap();
+
+        let result = game.count_score(Score::new(60).unwrap());
+
+        match result {
+            Err(Error::FailedPrecondition(reason, msg)) => {
+                assert_eq!(reason, Reason::GameAbandoned);
+                assert_eq!(msg, "Cannot count a score when game is abandoned");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_correct_score() {
+        let mut game = new_game(2, 301);
+
+        count_scores(&mut game, &[60, 45, 100]);
+
+        let correction = game
+            .correct_score(number(1), number(1), Score::new(20).unwrap())
+            .unwrap();
+
+        assert_eq!(correction.previous_score().points().value(), 60);
+        assert_eq!(correction.score().points().value(), 20);
+
+        match game.state() {
+            State::InProgress(state) => {
+                assert_eq!(state.player_number().value(), 2);
+                assert_eq!(state.round_number().value(), 2);
+                assert_eq!(state.points_to_win().value(), 256);
+            }
+            _ => unreachable!(),
+        }
+
+        let first_round = game.rounds().first().unwrap();
+
+        assert_eq!(first_round.player_score().score().points().value(), 20);
+    }
+
+    #[test]
+    fn test_correct_score_overthrows_later_visit() {
+        let mut game = new_game(1, 101);
+
+        count_scores(&mut game, &[60, 30]);
+
+        assert_eq!(points_to_win(&game), 11);
+
+        game.correct_score(number(1), number(1), Score::new(80).unwrap())
+            .unwrap();
+
+        let last_round = game.rounds().last().unwrap();
+
+        assert!(last_round.player_score().is_overthrow());
+        assert_eq!(points_to_win(&game), 21);
+    }
+
+    #[test]
+    fn test_correct_score_of_won_game() {
+        let mut game = new_game(1, 101);
+
+        count_scores(&mut game, &[60, 41]);
+
+        assert_eq!(game.winner().map(|number| number.value()), Some(1));
+        assert!(game.end_time().is_some());
+
+        game.correct_score(number(1), number(1), Score::new(50).unwrap())
+            .unwrap();
+
+        assert!(game.winner().is_none());
+        assert!(game.end_time().is_none());
+        assert_eq!(points_to_win(&game), 10);
+    }
+
+    #[test]
+    fn test_correct_score_finishing_game_early() {
+        let mut game = new_game(1, 101);
+
+        count_scores(&mut game, &[60, 20, 10]);
+
+        let result = game.correct_score(number(1), number(1), Score::new(81).unwrap());
+
+        match result {
+            Err(Error::FailedPrecondition(reason, msg)) => {
+                assert_eq!(reason, Reason::GameFinished);
+                assert_eq!(
+                    msg,
+                    "Corrected score finishes the game before round 3 of Player1"
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        assert_eq!(game.rounds().len(), 3);
+        assert_eq!(points_to_win(&game), 11);
+    }
+
+    #[test]
+    fn test_correct_score_of_missing_round() {
+        let mut game = new_game(2, 301);
+
+        count_scores(&mut game, &[60]);
+
+        let result = game.correct_score(number(1), number(2), Score::new(20).unwrap());
+
+        match result {
+            Err(Error::NotFound(reason, msg)) => {
+                assert_eq!(reason, Reason::RoundNotFound);
+                assert_eq!(msg, "Round 1 of Player2");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_cancel_last_score() {
+        let mut game = new_game(2, 301);
+
+        count_scores(&mut game, &[60, 45]);
+
+        game.cancel_last_score().unwrap();
+
+        assert_eq!(game.rounds().len(), 1);
+
+        match game.state() {
+            State::InProgress(state) => {
+                assert_eq!(state.player_number().value(), 2);
+                assert_eq!(state.round_number().value(), 1);
+                assert_eq!(state.points_to_win().value(), 301);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_cancel_winning_score() {
+        let mut game = new_game(1, 101);
+
+        count_scores(&mut game, &[60, 41]);
+
+        game.cancel_last_score().unwrap();
+
+        assert!(game.winner().is_none());
+        assert!(game.end_time().is_none());
+        assert_eq!(points_to_win(&game), 41);
+    }
+
+    #[test]
+    fn test_cancel_only_score() {
+        let mut game = new_game(2, 301);
+
+        count_scores(&mut game, &[60]);
+
+        game.cancel_last_score().unwrap();
+
+        assert!(game.rounds().is_empty());
+        assert!(game.start_time().is_none());
+        assert!(matches!(game.state(), State::NotStarted(_)));
+
+        let result = game.cancel_last_score();
+
+        match result {
+            Err(Error::FailedPrecondition(reason, _)) => {
+                assert_eq!(reason, Reason::GameNotStarted)
+            }
+            _ => unreachable!(),
+        }
+    }
 }
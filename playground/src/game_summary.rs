@@ -0,0 +1,140 @@
+use crate::{Error, Game, Number, Points, State};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Lightweight projection of a [`Game`] used for listings.
+/// Unlike [`Game`] it is loaded without rounds, so no score replay is needed.
+pub struct GameSummary {
+    create_time: DateTime<Utc>,
+    end_time: Option<DateTime<Utc>>,
+    id: Uuid,
+    players_number: Number,
+    points_limit: Points,
+    start_time: Option<DateTime<Utc>>,
+    state: State,
+    update_time: DateTime<Utc>,
+}
+
+pub struct LoadGameSummaryParameters {
+    pub abandon_reason: Option<String>,
+    pub abandon_time: Option<DateTime<Utc>>,
+    pub create_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub id: Uuid,
+    pub player_number: Option<Number>,
+    pub players_number: Number,
+    pub points_limit: Points,
+    pub points_to_win: Option<Points>,
+    pub round_number: Option<Number>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub update_time: DateTime<Utc>,
+    pub winner: Option<Number>,
+}
+
+impl GameSummary {
+    pub fn create_time(&self) -> DateTime<Utc> {
+        self.create_time
+    }
+
+    pub fn end_time(&self) -> Option<DateTime<Utc>> {
+        self.end_time
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn load(parameters: LoadGameSummaryParameters) -> Result<Self, Error> {
+        let LoadGameSummaryParameters {
+            abandon_reason,
+            abandon_time,
+            create_time,
+            end_time,
+            id,
+            player_number,
+            players_number,
+            points_limit,
+            points_to_win,
+            round_number,
+            start_time,
+            update_time,
+            winner,
+        } = parameters;
+
+        let state = match (abandon_time, winner, start_time) {
+            (Some(time), _, _) => State::abandoned(abandon_reason.unwrap_or_default(), time),
+            (None, Some(winner), _) => State::finished(winner),
+            (None, None, None) => State::not_started(points_to_win.unwrap_or(points_limit)),
+            (None, None, Some(_)) => {
+                let (Some(player_number), Some(round_number), Some(points_to_win)) =
+                    (player_number, round_number, points_to_win)
+                else {
+                    return Error::unexpected("Incomplete state of game in progress").into();
+                };
+
+                State::in_progress(player_number, round_number, points_to_win)
+            }
+        };
+
+        Ok(Self {
+            create_time,
+            end_time,
+            id,
+            players_number,
+            points_limit,
+            start_time,
+            state,
+            update_time,
+        })
+    }
+
+    pub fn players_number(&self) -> Number {
+        self.players_number
+    }
+
+    pub fn points_limit(&self) -> Points {
+        self.points_limit
+    }
+
+    pub fn start_time(&self) -> Option<DateTime<Utc>> {
+        self.start_time
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn update_time(&self) -> DateTime<Utc> {
+        self.update_time
+    }
+
+    pub fn winner(&self) -> Option<Number> {
+        match &self.state {
+            State::Finished(state) => Some(state.winner()),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<&Game> for GameSummary {
+    type Error = Error;
+
+    fn try_from(game: &Game) -> Result<Self, Self::Error> {
+        let (Some(id), Some(create_time), Some(update_time)) =
+            (game.id(), game.create_time(), game.update_time())
+        else {
+            return Error::unexpected("Summary of unsaved game").into();
+        };
+
+        Ok(Self {
+            create_time,
+            end_time: game.end_time(),
+            id,
+            players_number: game.players_number(),
+            points_limit: game.points_limit(),
+            start_time: game.start_time(),
+            state: game.state().clone(),
+            update_time,
+        })
+    }
+}
@@ -1,18 +1,28 @@
 mod error;
 mod game;
+mod game_summary;
 mod number;
+mod pace;
 mod player_score;
 mod points;
 mod score;
+mod score_correction;
+mod shot_clock;
+mod timeline;
 
 pub mod coordinator;
 
-pub use error::Error;
+pub use error::{Error, Reason};
 pub use game::{
-    FinishedState, Game, InProgressState, LoadGameParameters, NewGameParameters,
-    NewRoundParameters, NotStartedState, Round, State,
+    AbandonedState, ExpectedTurn, FinishedState, Game, InProgressState, LoadGameParameters,
+    NewGameParameters, NewRoundParameters, NotStartedState, Round, State,
 };
+pub use game_summary::{GameSummary, LoadGameSummaryParameters};
 pub use number::Number;
+pub use pace::Pace;
 pub use player_score::PlayerScore;
 pub use points::Points;
 pub use score::Score;
+pub use score_correction::{NewScoreCorrectionParameters, ScoreCorrection};
+pub use shot_clock::{LateVisitPolicy, NewShotClockParameters, ShotClock};
+pub use timeline::TimelineStep;
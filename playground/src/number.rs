@@ -1,4 +1,4 @@
-use crate::Error;
+use crate::{Error, Reason};
 use std::{fmt::Display, num::NonZeroUsize};
 
 #[derive(Clone, Copy, Hash, PartialEq, PartialOrd, Eq, Ord)]
@@ -17,6 +17,7 @@ impl Number {
 
     pub fn new(value: usize) -> Result<Self, Error> {
         let value = NonZeroUsize::new(value).ok_or(Error::InvalidArgument(
+            Reason::ZeroNumber,
             "Number must be greater than 0".to_string(),
         ))?;
 
@@ -0,0 +1,105 @@
+use crate::Game;
+use chrono::{DateTime, Duration, Utc};
+
+/// How fast a game is played, from the times of its visits.
+pub struct Pace {
+    average_visit_duration: Option<Duration>,
+    duration: Option<Duration>,
+}
+
+impl Game {
+    /// Visits without times, counted before times were kept, are left out.
+    pub fn pace(&self) -> Pace {
+        let times: Vec<DateTime<Utc>> = self.rounds().iter().filter_map(|r| r.time()).collect();
+
+        // A visit lasts from the previous visit, the first one has nothing to start from.
+        let visit_durations: Vec<Duration> =
+            times.windows(2).map(|pair| pair[1] - pair[0]).collect();
+
+        let average_visit_duration = (!visit_durations.is_empty()).then(|| {
+            visit_durations
+                .iter()
+                .fold(Duration::zero(), |sum, d| sum + *d)
+                / visit_durations.len() as i32
+        });
+
+        let duration = self
+            .start_time()
+            .zip(self.end_time().or(times.last().copied()))
+            .map(|(start_time, end_time)| end_time - start_time);
+
+        Pace {
+            average_visit_duration,
+            duration,
+        }
+    }
+}
+
+impl Pace {
+    /// Average time between consecutive visits.
+    pub fn average_visit_duration(&self) -> Option<Duration> {
+        self.average_visit_duration
+    }
+
+    /// Time from the first visit to the winning one, or to the last one so far.
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NewGameParameters, Number, Points, Score};
+
+    fn new_game() -> Game {
+        Game::new(NewGameParameters {
+            players_number: Number::new(2).unwrap(),
+            points_limit: Points::new(301),
+            shot_clock: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pace() {
+        let mut game = new_game();
+        let start_time = Utc::now();
+
+        for seconds in [0, 10, 30] {
+            let time = start_time + Duration::seconds(seconds);
+
+            game.count_visit(Score::new(60).unwrap(), Some(time), false)
+                .unwrap();
+        }
+
+        let pace = game.pace();
+
+        assert_eq!(pace.average_visit_duration(), Some(Duration::seconds(15)));
+        assert_eq!(pace.duration(), Some(Duration::seconds(30)));
+    }
+
+    #[test]
+    fn test_pace_of_not_started_game() {
+        let pace = new_game().pace();
+
+        assert!(pace.average_visit_duration().is_none());
+        assert!(pace.duration().is_none());
+    }
+
+    #[test]
+    fn test_pace_leaves_out_visits_without_times() {
+        let mut game = new_game();
+        let start_time = Utc::now();
+
+        game.count_visit(Score::new(60).unwrap(), Some(start_time), false)
+            .unwrap();
+        game.count_visit(Score::new(60).unwrap(), None, false)
+            .unwrap();
+
+        let pace = game.pace();
+
+        assert!(pace.average_visit_duration().is_none());
+        assert_eq!(pace.duration(), Some(Duration::zero()));
+    }
+}
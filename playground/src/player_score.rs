@@ -1,5 +1,6 @@
 use crate::{Points, Score};
 
+#[derive(Clone)]
 pub enum PlayerScore {
     Regular(Score),
     Overthrow(Score),
@@ -1,10 +1,11 @@
-use crate::{Error, Points};
+use crate::{Error, Points, Reason};
 
 /// Player can not score more than 180 points at a time.
 /// Maximum points per one dart is 60.
 /// Player throws 3 darts per one round which is equal to 180 points.
 const POINTS_LIMIT: u16 = 180;
 
+#[derive(Clone)]
 pub struct Score(Points);
 
 impl Score {
@@ -15,7 +16,7 @@ impl Score {
                 POINTS_LIMIT, points
             );
 
-            return Error::invalid_argument(description).into();
+            return Error::invalid_argument(Reason::ScoreOverLimit, description).into();
         };
 
         self.0 = points;
@@ -59,13 +60,15 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_new_score_over_limit() {
         let result = Score::new(POINTS_LIMIT + 1);
 
         assert!(result.is_err());
 
         match result {
-            Err(Error::InvalidArgument(msg)) => {
+            Err(Error::InvalidArgument(reason, msg)) => {
+                assert_eq!(reason, Reason::ScoreOverLimit);
                 assert_eq!(
                     msg,
                     "The maximum number of points allowed is 180. Given: 181"
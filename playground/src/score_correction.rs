@@ -0,0 +1,60 @@
+use crate::{Number, Score};
+use chrono::{DateTime, Utc};
+
+/// Score of a counted visit replaced with another one, kept as history of the game.
+#[derive(Clone)]
+pub struct ScoreCorrection {
+    player_number: Number,
+    previous_score: Score,
+    round_number: Number,
+    score: Score,
+    time: DateTime<Utc>,
+}
+
+pub struct NewScoreCorrectionParameters {
+    pub player_number: Number,
+    pub previous_score: Score,
+    pub round_number: Number,
+    pub score: Score,
+    pub time: DateTime<Utc>,
+}
+
+impl ScoreCorrection {
+    pub fn new(parameters: NewScoreCorrectionParameters) -> Self {
+        let NewScoreCorrectionParameters {
+            player_number,
+            previous_score,
+            round_number,
+            score,
+            time,
+        } = parameters;
+
+        Self {
+            player_number,
+            previous_score,
+            round_number,
+            score,
+            time,
+        }
+    }
+
+    pub fn player_number(&self) -> Number {
+        self.player_number
+    }
+
+    pub fn previous_score(&self) -> &Score {
+        &self.previous_score
+    }
+
+    pub fn round_number(&self) -> Number {
+        self.round_number
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
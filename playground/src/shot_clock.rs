@@ -0,0 +1,167 @@
+use crate::{Error, Game, Reason, State};
+use chrono::{DateTime, Duration, Utc};
+
+/// Longest turn a shot clock allows, in minutes.
+const TURN_TIME_LIMIT_MAX: i64 = 60;
+
+/// Time limit of a turn, counted from the visit before it.
+#[derive(Clone, Copy)]
+pub struct ShotClock {
+    late_visit_policy: LateVisitPolicy,
+    turn_time_limit: Duration,
+}
+
+/// What happens to a visit counted after its turn ran out of time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LateVisitPolicy {
+    /// The score counts and the visit is marked late.
+    Flag,
+    /// The visit is marked late and scores zero.
+    ScoreZero,
+}
+
+pub struct NewShotClockParameters {
+    pub late_visit_policy: LateVisitPolicy,
+    pub turn_time_limit: Duration,
+}
+
+impl ShotClock {
+    pub fn new(parameters: NewShotClockParameters) -> Result<Self, Error> {
+        let NewShotClockParameters {
+            late_visit_policy,
+            turn_time_limit,
+        } = parameters;
+
+        if turn_time_limit <= Duration::zero()
+            || turn_time_limit > Duration::minutes(TURN_TIME_LIMIT_MAX)
+        {
+            let description = format!(
+                "Turn time limit must be positive and at most {TURN_TIME_LIMIT_MAX} minutes"
+            );
+
+            return Error::invalid_argument(Reason::InvalidTurnTimeLimit, description).into();
+        }
+
+        Ok(Self {
+            late_visit_policy,
+            turn_time_limit,
+        })
+    }
+
+    pub fn late_visit_policy(&self) -> LateVisitPolicy {
+        self.late_visit_policy
+    }
+
+    pub fn turn_time_limit(&self) -> Duration {
+        self.turn_time_limit
+    }
+}
+
+impl Game {
+    /// Whether a visit counted at `time` comes after the current turn ran out.
+    pub(crate) fn is_late(&self, time: DateTime<Utc>) -> bool {
+        self.shot_clock()
+            .zip(self.turn_start_time())
+            .is_some_and(|(shot_clock, start_time)| time - start_time > shot_clock.turn_time_limit)
+    }
+
+    /// Time the current turn has left at `now`, zero once it ran out.
+    pub fn remaining_turn_time(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let shot_clock = self.shot_clock()?;
+        let start_time = self.turn_start_time()?;
+
+        Some((shot_clock.turn_time_limit - (now - start_time)).max(Duration::zero()))
+    }
+
+    /// When the clock of the current turn started, with the visit before it. The opening
+    /// visit is not timed, there is nothing telling when players step up to the board.
+    pub fn turn_start_time(&self) -> Option<DateTime<Utc>> {
+        self.shot_clock()?;
+
+        match self.state() {
+            State::InProgress(_) => self.rounds().last()?.time(),
+            State::NotStarted(_) | State::Finished(_) | State::Abandoned(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NewGameParameters, Number, Points, Score};
+
+    fn new_shot_clock(turn_time_limit: Duration) -> Result<ShotClock, Error> {
+        ShotClock::new(NewShotClockParameters {
+            late_visit_policy: LateVisitPolicy::Flag,
+            turn_time_limit,
+        })
+    }
+
+    fn new_game(shot_clock: Option<ShotClock>) -> Game {
+        Game::new(NewGameParameters {
+            players_number: Number::new(2).unwrap(),
+            points_limit: Points::new(301),
+            shot_clock,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new_shot_clock() {
+        assert!(new_shot_clock(Duration::seconds(30)).is_ok());
+        assert!(new_shot_clock(Duration::minutes(TURN_TIME_LIMIT_MAX)).is_ok());
+    }
+
+    #[test]
+    fn test_new_shot_clock_out_of_limits() {
+        for turn_time_limit in [
+            Duration::zero(),
+            Duration::seconds(-1),
+            Duration::minutes(TURN_TIME_LIMIT_MAX) + Duration::seconds(1),
+        ] {
+            match new_shot_clock(turn_time_limit) {
+                Err(Error::InvalidArgument(reason, _)) => {
+                    assert_eq!(reason, Reason::InvalidTurnTimeLimit)
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_turn_time() {
+        let mut game = new_game(Some(new_shot_clock(Duration::seconds(30)).unwrap()));
+        let time = Utc::now();
+
+        assert!(game.turn_start_time().is_none());
+        assert!(!game.is_late(time + Duration::hours(1)));
+
+        game.count_visit(Score::new(60).unwrap(), Some(time), false)
+            .unwrap();
+
+        assert_eq!(game.turn_start_time(), Some(time));
+        assert!(!game.is_late(time + Duration::seconds(30)));
+        assert!(game.is_late(time + Duration::seconds(31)));
+        assert_eq!(
+            game.remaining_turn_time(time + Duration::seconds(10)),
+            Some(Duration::seconds(20))
+        );
+        assert_eq!(
+            game.remaining_turn_time(time + Duration::seconds(40)),
+            Some(Duration::zero())
+        );
+    }
+
+    #[test]
+    fn test_turn_time_without_shot_clock() {
+        let mut game = new_game(None);
+        let time = Utc::now();
+
+        game.count_visit(Score::new(60).unwrap(), Some(time), false)
+            .unwrap();
+
+        assert!(game.turn_start_time().is_none());
+        assert!(game.remaining_turn_time(time).is_none());
+        assert!(!game.is_late(time + Duration::hours(1)));
+    }
+}
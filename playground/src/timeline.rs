@@ -0,0 +1,163 @@
+use crate::{game::visit_time, Error, Game, NewGameParameters, Points, Round, State};
+use chrono::{DateTime, Utc};
+
+/// Game right after one of its visits.
+pub struct TimelineStep {
+    points_to_win: Vec<Points>,
+    round: Round,
+    state: State,
+    time: Option<DateTime<Utc>>,
+}
+
+impl Game {
+    /// Replays the game visit by visit, in the order visits were counted.
+    pub fn timeline(&self) -> Result<Vec<TimelineStep>, Error> {
+        let mut replayed = Game::new(NewGameParameters {
+            players_number: self.players_number(),
+            points_limit: self.points_limit(),
+            shot_clock: self.shot_clock().copied(),
+        })?;
+        let mut steps = Vec::with_capacity(self.rounds().len());
+
+        for (index, round) in self.rounds().iter().enumerate() {
+            let time = visit_time(round, index, self.start_time());
+
+            replayed.count_visit(round.player_score().score().clone(), time, round.is_late())?;
+
+            steps.push(TimelineStep {
+                points_to_win: points_to_win(&replayed),
+                round: round.clone(),
+                state: replayed.state().clone(),
+                time: round.time(),
+            });
+        }
+
+        // Visits counted before times were kept only have the times of the game.
+        if let Some(step) = steps.first_mut().filter(|step| step.time.is_none()) {
+            step.time = self.start_time();
+        }
+
+        if let Some(step) = steps
+            .last_mut()
+            .filter(|step| step.time.is_none() && self.end_time().is_some())
+        {
+            step.time = self.end_time();
+        }
+
+        Ok(steps)
+    }
+}
+
+impl TimelineStep {
+    /// Points each player still needs, by player number starting from 1.
+    pub fn points_to_win(&self) -> &[Points] {
+        &self.points_to_win
+    }
+
+    pub fn round(&self) -> &Round {
+        &self.round
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        self.time
+    }
+}
+
+fn points_to_win(game: &Game) -> Vec<Points> {
+    (1..=game.players_number().value())
+        .map(|player_number| {
+            let points: Points = game
+                .rounds()
+                .iter()
+                .filter(|round| round.player_number().value() == player_number)
+                .map(|round| round.player_score().game_points())
+                .sum();
+
+            Points::new(game.points_limit().value() - points.value())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LoadGameParameters, NewRoundParameters, Number, PlayerScore, Score};
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    fn points_values(step: &TimelineStep) -> Vec<u16> {
+        step.points_to_win().iter().map(Points::value).collect()
+    }
+
+    #[test]
+    fn test_timeline() {
+        let mut game = Game::new(NewGameParameters {
+            players_number: Number::new(2).unwrap(),
+            points_limit: Points::new(301),
+            shot_clock: None,
+        })
+        .unwrap();
+
+        for points in [60, 45, 100] {
+            game.count_score(Score::new(points).unwrap()).unwrap();
+        }
+
+        let steps = game.timeline().unwrap();
+
+        assert_eq!(steps.len(), 3);
+        assert_eq!(points_values(&steps[0]), [241, 301]);
+        assert_eq!(points_values(&steps[1]), [241, 256]);
+        assert_eq!(points_values(&steps[2]), [141, 256]);
+        assert!(steps.iter().all(|step| step.time().is_some()));
+
+        match steps[2].state() {
+            State::InProgress(state) => assert_eq!(state.player_number().value(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_timeline_of_visits_without_times() {
+        let start_time = Utc::now();
+        let end_time = start_time + Duration::minutes(2);
+
+        let rounds = [60, 20, 21]
+            .into_iter()
+            .enumerate()
+            .map(|(index, points)| {
+                Round::new(NewRoundParameters {
+                    late: false,
+                    number: Number::new(index + 1).unwrap(),
+                    player_number: Number::one(),
+                    player_score: PlayerScore::regular(Score::new(points).unwrap()),
+                    time: None,
+                })
+            })
+            .collect();
+
+        let game = Game::load(LoadGameParameters {
+            abandon_reason: None,
+            abandon_time: None,
+            create_time: start_time,
+            end_time: Some(end_time),
+            id: Uuid::nil(),
+            players_number: Number::one(),
+            points_limit: Points::new(101),
+            rounds,
+            shot_clock: None,
+            start_time: Some(start_time),
+            update_time: end_time,
+        })
+        .unwrap();
+
+        let steps = game.timeline().unwrap();
+        let times: Vec<_> = steps.iter().map(TimelineStep::time).collect();
+
+        assert_eq!(times, [Some(start_time), None, Some(end_time)]);
+        assert!(matches!(steps[2].state(), State::Finished(_)));
+    }
+}
@@ -0,0 +1,302 @@
+//! Settings shared by the `api` and `api_mock` servers.
+//!
+//! Every setting can be given as a command line flag, as a `DARTS_*` environment variable
+//! or as a key of a TOML configuration file. Flags take precedence over environment
+//! variables, which take precedence over the file.
+
+use anyhow::Context;
+use serde::Deserialize;
+use std::{
+    fs,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tonic::transport::{Identity, Server, ServerTlsConfig};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+const DEFAULT_DATABASE_POOL_SIZE: u32 = 10;
+const DEFAULT_DRAIN_TIMEOUT_SECONDS: u64 = 5;
+const DEFAULT_GRPC_PORT: u16 = 50051;
+const DEFAULT_HTTP_PORT: u16 = 8080;
+
+#[derive(clap::Args)]
+pub struct ServerArgs {
+    /// TOML file with default values for the other settings
+    #[arg(long, env = "DARTS_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// IP address both listeners bind to [default: ::]
+    #[arg(long, env = "DARTS_ADDRESS")]
+    address: Option<IpAddr>,
+
+    /// Role of requests without a bearer token (viewer, scorer or admin). Rejected when omitted
+    #[arg(long, env = "DARTS_ANONYMOUS_ROLE")]
+    anonymous_role: Option<String>,
+
+    /// Origin allowed to call the gRPC-Web API, may be repeated. Any origin is allowed when omitted
+    #[arg(
+        long = "cors-allowed-origin",
+        env = "DARTS_CORS_ALLOWED_ORIGINS",
+        value_delimiter = ','
+    )]
+    cors_allowed_origins: Vec<String>,
+
+    /// Maximum number of database connections [default: 10]
+    #[arg(long, env = "DARTS_DATABASE_POOL_SIZE")]
+    database_pool_size: Option<u32>,
+
+    #[arg(long, env = "DARTS_DATABASE_URL")]
+    database_url: Option<String>,
+
+    /// Seconds in-flight requests may take to finish after SIGINT or SIGTERM [default: 5]
+    #[arg(long, env = "DARTS_DRAIN_TIMEOUT")]
+    drain_timeout: Option<u64>,
+
+    /// Port of the gRPC and gRPC-Web API [default: 50051]
+    #[arg(long, env = "DARTS_GRPC_PORT")]
+    grpc_port: Option<u16>,
+
+    /// Port of the HTTP JSON gateway [default: 8080]
+    #[arg(long, env = "DARTS_HTTP_PORT")]
+    http_port: Option<u16>,
+
+    /// [default: text]
+    #[arg(long, env = "DARTS_LOG_FORMAT")]
+    log_format: Option<LogFormat>,
+
+    /// [default: info]
+    #[arg(long, env = "DARTS_LOG_LEVEL")]
+    log_level: Option<LogLevel>,
+
+    /// PEM certificate chain, enables TLS on the gRPC port together with the key
+    #[arg(long, env = "DARTS_TLS_CERT", requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+
+    /// PEM private key of the certificate
+    #[arg(long, env = "DARTS_TLS_KEY", requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+}
+
+pub struct ServerConfig {
+    pub anonymous_role: Option<String>,
+    pub cors_allowed_origins: Vec<String>,
+    pub database_pool_size: u32,
+    pub database_url: Option<String>,
+    pub drain_timeout: Duration,
+    pub grpc_address: SocketAddr,
+    pub http_address: SocketAddr,
+    pub log_format: LogFormat,
+    pub log_level: LogLevel,
+    pub tls: Option<TlsConfig>,
+}
+
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+/// Text is meant for terminals, JSON for log collectors.
+#[derive(Clone, Copy, Default, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, Default, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    address: Option<IpAddr>,
+    anonymous_role: Option<String>,
+    cors_allowed_origins: Option<Vec<String>>,
+    database_pool_size: Option<u32>,
+    database_url: Option<String>,
+    drain_timeout: Option<u64>,
+    grpc_port: Option<u16>,
+    http_port: Option<u16>,
+    log_format: Option<LogFormat>,
+    log_level: Option<LogLevel>,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+}
+
+impl ServerConfig {
+    /// Builds a gRPC server, with TLS when a certificate is configured.
+    pub fn grpc_server(&self) -> anyhow::Result<Server> {
+        let mut server = Server::builder();
+
+        if let Some(tls) = &self.tls {
+            server = server.tls_config(tls.server_tls_config()?)?;
+        }
+
+        Ok(server)
+    }
+
+    pub fn grpc_url(&self) -> String {
+        let scheme = if self.tls.is_some() { "https" } else { "http" };
+
+        format!("{scheme}://{}", self.grpc_address)
+    }
+
+    /// Installs the global subscriber. Closed spans are logged with their durations.
+    pub fn init_logging(&self) {
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::from(self.log_level))
+            .with_span_events(FmtSpan::CLOSE);
+
+        match self.log_format {
+            LogFormat::Text => subscriber.init(),
+            LogFormat::Json => subscriber.json().init(),
+        }
+    }
+
+    pub fn load(args: ServerArgs) -> anyhow::Result<Self> {
+        let file = match &args.config {
+            Some(path) => ConfigFile::read(path)?,
+            None => ConfigFile::default(),
+        };
+
+        Self::merge(args, file)
+    }
+
+    fn merge(args: ServerArgs, file: ConfigFile) -> anyhow::Result<Self> {
+        let address = args
+            .address
+            .or(file.address)
+            .unwrap_or(IpAddr::V6(Ipv6Addr::UNSPECIFIED));
+        let grpc_port = args
+            .grpc_port
+            .or(file.grpc_port)
+            .unwrap_or(DEFAULT_GRPC_PORT);
+        let http_port = args
+            .http_port
+            .or(file.http_port)
+            .unwrap_or(DEFAULT_HTTP_PORT);
+
+        let cors_allowed_origins = if args.cors_allowed_origins.is_empty() {
+            file.cors_allowed_origins.unwrap_or_default()
+        } else {
+            args.cors_allowed_origins
+        };
+
+        let tls = match (
+            args.tls_cert.or(file.tls_cert),
+            args.tls_key.or(file.tls_key),
+        ) {
+            (Some(cert), Some(key)) => Some(TlsConfig { cert, key }),
+            (None, None) => None,
+            _ => anyhow::bail!("TLS needs both a certificate and a key"),
+        };
+
+        Ok(Self {
+            anonymous_role: args.anonymous_role.or(file.anonymous_role),
+            cors_allowed_origins,
+            database_pool_size: args
+                .database_pool_size
+                .or(file.database_pool_size)
+                .unwrap_or(DEFAULT_DATABASE_POOL_SIZE),
+            database_url: args.database_url.or(file.database_url),
+            drain_timeout: Duration::from_secs(
+                args.drain_timeout
+                    .or(file.drain_timeout)
+                    .unwrap_or(DEFAULT_DRAIN_TIMEOUT_SECONDS),
+            ),
+            grpc_address: SocketAddr::new(address, grpc_port),
+            http_address: SocketAddr::new(address, http_port),
+            log_format: args.log_format.or(file.log_format).unwrap_or_default(),
+            log_level: args.log_level.or(file.log_level).unwrap_or_default(),
+            tls,
+        })
+    }
+}
+
+impl TlsConfig {
+    fn server_tls_config(&self) -> anyhow::Result<ServerTlsConfig> {
+        let cert = fs::read(&self.cert)
+            .with_context(|| format!("Reading TLS certificate {}", self.cert.display()))?;
+        let key = fs::read(&self.key)
+            .with_context(|| format!("Reading TLS key {}", self.key.display()))?;
+
+        Ok(ServerTlsConfig::new().identity(Identity::from_pem(cert, key)))
+    }
+}
+
+impl ConfigFile {
+    fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Reading config file {}", path.display()))?;
+
+        toml::from_str(&contents).with_context(|| format!("Parsing config file {}", path.display()))
+    }
+}
+
+impl From<LogLevel> for tracing::Level {
+    fn from(value: LogLevel) -> Self {
+        match value {
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Trace => Self::TRACE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        server: ServerArgs,
+    }
+
+    fn args(flags: &[&str]) -> ServerArgs {
+        Cli::parse_from(std::iter::once("server").chain(flags.iter().copied())).server
+    }
+
+    #[test]
+    fn test_flags_override_config_file() {
+        let file: ConfigFile = toml::from_str(
+            r#"
+            address = "127.0.0.1"
+            grpc_port = 50052
+            http_port = 8081
+            log_level = "debug"
+            "#,
+        )
+        .unwrap();
+
+        let config = ServerConfig::merge(args(&["--grpc-port", "50053"]), file).unwrap();
+
+        assert_eq!(config.grpc_address, "127.0.0.1:50053".parse().unwrap());
+        assert_eq!(config.http_address, "127.0.0.1:8081".parse().unwrap());
+        assert_eq!(
+            tracing::Level::from(config.log_level),
+            tracing::Level::DEBUG
+        );
+        assert_eq!(config.database_pool_size, DEFAULT_DATABASE_POOL_SIZE);
+    }
+
+    #[test]
+    fn test_tls_requires_certificate_and_key() {
+        let file: ConfigFile = toml::from_str(r#"tls_cert = "server.pem""#).unwrap();
+
+        assert!(ServerConfig::merge(args(&[]), file).is_err());
+    }
+}